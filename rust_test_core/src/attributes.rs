@@ -1,9 +1,13 @@
 pub(crate) mod common;
 mod test_params;
-mod test_params_source;
+mod test_case;
+pub(crate) mod test_params_source;
 mod test_fixture;
+pub(crate) mod test_matrix;
 pub use {
     test_params::*,
+    test_case::*,
     test_params_source::*,
-    test_fixture::*
+    test_fixture::*,
+    test_matrix::*
 };
\ No newline at end of file
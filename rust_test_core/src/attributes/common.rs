@@ -2,8 +2,9 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
+use syn::parse::ParseStream;
 use syn::spanned::Spanned;
-use syn::{Expr, ItemFn, Lit, Type, LitStr, Member, Pat};
+use syn::{Expr, GenericArgument, ItemFn, Lit, Type, LitStr, Member, Pat, PathArguments};
 
 pub fn check_json_compatibility(
     input_fn: &ItemFn,
@@ -77,16 +78,22 @@ pub fn check_json_compatibility(
     Ok(())
 }
 
-fn validate_type_match(ty: &Type, value: &Value, span: Span) -> syn::Result<()> {
+pub(crate) fn validate_type_match(ty: &Type, value: &Value, span: Span) -> syn::Result<()> {
     match ty {
         Type::Path(tp) => {
             if let Some(segment) = tp.path.segments.last() {
                 let name = segment.ident.to_string();
                 match name.as_str() {
-                    "u32" | "i32" | "u64" | "i64" | "usize" | "isize" | "u8" | "i8" | "u16" | "i16" => {
+                    "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => {
                         if !value.is_number() {
                             return Err(syn::Error::new(span, format!("Expected number for type {}, but got: {}", name, value)));
                         }
+                        let Some(digits) = integer_digits(value) else {
+                            return Err(syn::Error::new(span, format!("Expected integer for type {}, but got: {}", name, value)));
+                        };
+                        if !int_fits(&digits, &name) {
+                            return Err(syn::Error::new(span, format!("Value {} is out of range for type {}", digits, name)));
+                        }
                     }
                     "f32" | "f64" => {
                         if !value.is_number() {
@@ -98,6 +105,11 @@ fn validate_type_match(ty: &Type, value: &Value, span: Span) -> syn::Result<()>
                             return Err(syn::Error::new(span, format!("Expected string for type String, but got: {}", value)));
                         }
                     }
+                    "char" => {
+                        if !value.as_str().is_some_and(|s| s.chars().count() == 1) {
+                            return Err(syn::Error::new(span, format!("Expected a single character for type char, but got: {}", value)));
+                        }
+                    }
                     "bool" => {
                         if !value.is_boolean() {
                             return Err(syn::Error::new(span, format!("Expected boolean for type bool, but got: {}", value)));
@@ -128,6 +140,203 @@ fn validate_type_match(ty: &Type, value: &Value, span: Span) -> syn::Result<()>
     Ok(())
 }
 
+/// Converts the tuple structs among the values given for the parameter at `index` of `input_fn`.
+///
+/// A bare call like `Meters(5)` is converted like an enum variant, `{"Meters": 5}`, since it can be
+/// a variant imported with `use Shape::*`. It is a tuple struct when it names the parameter type,
+/// or a type the parameter holds, like `Option<Meters>` or `Vec<Meters>`, and is unwrapped to `5`.
+pub(crate) fn unwrap_tuple_structs(input_fn: &ItemFn, index: usize, values: &mut [ValueWithSpan]) {
+    if let Some(syn::FnArg::Typed(pat_type)) = input_fn.sig.inputs.iter().nth(index) {
+        for value in values {
+            unwrap_tuple_struct(&pat_type.ty, &mut value.value);
+        }
+    }
+}
+
+/// Unwraps the values converted from calls named after `ty` or the types it holds.
+pub(crate) fn unwrap_tuple_struct(ty: &Type, value: &mut Value) {
+    match ty {
+        Type::Reference(tr) => unwrap_tuple_struct(&tr.elem, value),
+        Type::Paren(tp) => unwrap_tuple_struct(&tp.elem, value),
+        Type::Group(tg) => unwrap_tuple_struct(&tg.elem, value),
+        Type::Array(ta) => value.as_array_mut().into_iter().flatten().for_each(|v| unwrap_tuple_struct(&ta.elem, v)),
+        Type::Slice(ts) => value.as_array_mut().into_iter().flatten().for_each(|v| unwrap_tuple_struct(&ts.elem, v)),
+        Type::Tuple(tt) => {
+            if let Some(elements) = value.as_array_mut().filter(|a| a.len() == tt.elems.len()) {
+                for (elem_ty, v) in tt.elems.iter().zip(elements) {
+                    unwrap_tuple_struct(elem_ty, v);
+                }
+            }
+        }
+        Type::Path(tp) => {
+            if let Some(segment) = tp.path.segments.last() {
+                if let Value::Object(object) = value {
+                    if object.len() == 1 && object.contains_key(&segment.ident.to_string()) {
+                        *value = object.values().next().unwrap().clone();
+                        return;
+                    }
+                }
+                // `Option<T>`, `Vec<T>`, `Box<T>`: the values of the type they hold
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let (1, Some(GenericArgument::Type(inner))) = (args.args.len(), args.args.first()) {
+                        match value {
+                            Value::Array(elements) if segment.ident != "Box" && segment.ident != "Option" => {
+                                elements.iter_mut().for_each(|v| unwrap_tuple_struct(inner, v))
+                            }
+                            other => unwrap_tuple_struct(inner, other),
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Checks the values given for the parameter at `index` of `input_fn` against its type.
+pub(crate) fn validate_param_values(input_fn: &ItemFn, index: usize, values: &[ValueWithSpan]) -> syn::Result<()> {
+    if let Some(syn::FnArg::Typed(pat_type)) = input_fn.sig.inputs.iter().nth(index) {
+        // The compiler checks the types of constants
+        for value in values.iter().filter(|value| value.constant(0).is_none()) {
+            validate_type_match(&pat_type.ty, &value.value, value.span)?;
+        }
+    }
+    Ok(())
+}
+
+/// Converts an integer into a JSON value. Numbers keep their digits with the `arbitrary_precision`
+/// feature of `serde_json`, so integers beyond 64 bits stay exact.
+pub(crate) fn int_to_value(n: i128) -> Value {
+    Value::Number(serde_json::Number::from_i128(n).expect("integers of any size fit into a number"))
+}
+
+/// The decimal digits of an integer value, with a leading `-` if it is negative.
+fn integer_digits(value: &Value) -> Option<String> {
+    let Value::Number(n) = value else {
+        return None;
+    };
+    let digits = n.as_str();
+    digits
+        .strip_prefix('-')
+        .unwrap_or(digits)
+        .bytes()
+        .all(|b| b.is_ascii_digit())
+        .then(|| digits.to_string())
+}
+
+/// Whether the integer given by its decimal digits fits into the integer type `name`.
+fn int_fits(digits: &str, name: &str) -> bool {
+    macro_rules! fits {
+        ($($t:ident),*) => {
+            match name {
+                $(stringify!($t) => digits.parse::<$t>().is_ok(),)*
+                _ => true,
+            }
+        };
+    }
+    fits!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize)
+}
+
+/// Whether a path names a constant or static rather than an enum unit variant,
+/// going by its last segment being written in `SCREAMING_SNAKE_CASE`.
+///
+/// A segment under a type, `Method::GET`, is taken for an all-caps enum variant. Only modules and
+/// primitive types, `consts::LIMITS` or `u8::MAX`, are lowercase, so their constants are recognized.
+fn is_constant_path(path: &syn::Path) -> bool {
+    let mut segments = path.segments.iter().rev();
+    let is_screaming = segments.next().is_some_and(|segment| {
+        let name = segment.ident.to_string();
+        name.len() > 1 && name.chars().any(|c| c.is_ascii_uppercase()) && !name.chars().any(char::is_lowercase)
+    });
+    let under_type = segments
+        .next()
+        .is_some_and(|parent| parent.ident.to_string().starts_with(|c: char| c.is_ascii_uppercase()));
+    is_screaming && !under_type
+}
+
+/// Whether an expression is passed to the test function as it is: a constant or static
+/// such as `LIMITS`, `consts::LIMITS` or `&LIMITS`, or one wrapped in `Some(...)`.
+fn is_constant_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Path(expr_path) => is_constant_path(&expr_path.path),
+        Expr::Reference(reference) => matches!(&*reference.expr, Expr::Path(p) if is_constant_path(&p.path)),
+        Expr::Call(call) => {
+            matches!(&*call.func, Expr::Path(p) if p.path.is_ident("Some"))
+                && call.args.len() == 1
+                && is_constant_expr(&call.args[0])
+        }
+        Expr::Paren(paren) => is_constant_expr(&paren.expr),
+        Expr::Group(group) => is_constant_expr(&group.expr),
+        _ => false,
+    }
+}
+
+/// The test name suffix of a constant, from the segments of its path, e.g. `consts_limits`.
+fn constant_suffix(expr: &Expr) -> String {
+    match expr {
+        Expr::Path(expr_path) => expr_path
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string().to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        Expr::Reference(reference) => constant_suffix(&reference.expr),
+        Expr::Call(call) => call.args.first().map(constant_suffix).unwrap_or_default(),
+        Expr::Paren(paren) => constant_suffix(&paren.expr),
+        Expr::Group(group) => constant_suffix(&group.expr),
+        _ => String::new(),
+    }
+}
+
+/// A test case value passed to the test function as the expression it is written as.
+/// Its JSON value is `null`, which is never deserialized.
+fn constant_with_span(expr: &Expr) -> ValueWithSpan {
+    ValueWithSpan {
+        value: Value::Null,
+        span: expr.span(),
+        suffix: Some(constant_suffix(expr)),
+        expected: None,
+        ignore: None,
+        should_panic: None,
+        raw: None,
+        constants: vec![Some(expr.clone())],
+        expected_constant: None,
+        csv_row: None,
+    }
+}
+
+/// Converts a constant or static given with `const:`, `LIMITS`, `consts::LIMITS` or `&LIMITS`,
+/// whatever its name looks like.
+pub(crate) fn constant_to_value_with_span(expr: &Expr) -> syn::Result<ValueWithSpan> {
+    let target = match expr {
+        Expr::Reference(reference) => &*reference.expr,
+        _ => expr,
+    };
+    if !matches!(target, Expr::Path(_)) {
+        return Err(syn::Error::new_spanned(
+            expr,
+            "Expected the path of a constant or static, e.g. `const: LIMITS` or `const: &LIMITS`",
+        ));
+    }
+    Ok(constant_with_span(expr))
+}
+
+/// Rejects a constant given as a part of a larger value, which can't be deserialized.
+pub(crate) fn reject_constant(expr: &Expr) -> syn::Result<()> {
+    if is_constant_expr(expr) {
+        return Err(constant_error(expr));
+    }
+    Ok(())
+}
+
+fn constant_error(expr: &Expr) -> syn::Error {
+    syn::Error::new_spanned(
+        expr,
+        "Constants can only be passed as a whole argument, use #[test_case] to build values from them",
+    )
+}
+
 fn extract_fields_from_tokens(tokens: proc_macro2::TokenStream, param_names: &[String], accessed_fields: &mut Vec<String>) {
     let mut iter = tokens.into_iter().peekable();
     while let Some(token) = iter.next() {
@@ -206,9 +415,50 @@ pub struct ValueWithSpan {
     pub value: Value,
     pub span: Span,
     pub suffix: Option<String>,
+    /// Value the test function is expected to return for this test case.
+    pub expected: Option<Value>,
+    /// Marks the generated test with `#[ignore]`, with the reason unless it is empty.
+    pub ignore: Option<String>,
+    /// Marks the generated test with `#[should_panic]`, with the expected message unless it is empty.
+    pub should_panic: Option<String>,
+    /// Arguments given as Rust expressions by `#[test_case]`, passed to the test function as they are.
+    pub raw: Option<RawCase>,
+    /// Constants and statics passed to the test function as they are, by argument index.
+    /// The value holds `null` in their place.
+    pub constants: Vec<Option<Expr>>,
+    /// Constant the returned value is compared with, given with `expect = ...` instead of `expected`.
+    pub expected_constant: Option<Expr>,
+    /// Line of the CSV row the case was read from, if its cells are deserialized into the fields of a struct.
+    pub csv_row: Option<usize>,
+}
+
+impl ValueWithSpan {
+    /// The constant passed for the argument at `index`, if any.
+    pub(crate) fn constant(&self, index: usize) -> Option<&Expr> {
+        self.constants.get(index).and_then(Option::as_ref)
+    }
+}
+
+/// A test case of `#[test_case]`, which is not converted to JSON.
+#[derive(Clone)]
+pub struct RawCase {
+    pub args: Vec<Expr>,
+    /// Expression the returned value is compared with, given with `expect = ...`.
+    pub expected: Option<Expr>,
+}
+
+impl RawCase {
+    /// The arguments as they are written, to tell duplicate test cases apart.
+    fn args_string(&self) -> String {
+        let args = &self.args;
+        quote!(#(#args),*).to_string()
+    }
 }
 
 pub fn expr_to_value_with_span(expr: &Expr) -> syn::Result<ValueWithSpan> {
+    if is_constant_expr(expr) {
+        return Ok(constant_with_span(expr));
+    }
     let (value, suffix) = expr_to_value_and_suffix(expr)?;
     let span = match expr {
         Expr::Path(p) => p.path.span(),
@@ -218,9 +468,126 @@ pub fn expr_to_value_with_span(expr: &Expr) -> syn::Result<ValueWithSpan> {
         value,
         span,
         suffix: Some(suffix),
+        expected: None,
+        ignore: None,
+        should_panic: None,
+        raw: None,
+        constants: Vec::new(),
+        expected_constant: None,
+        csv_row: None,
     })
 }
 
+/// Most test cases a range, or the combinations of ranges of one attribute, may expand into.
+pub(crate) const MAX_EXPANDED_CASES: usize = 1000;
+
+/// Converts an expression into test case values: one per element for `1..=100`, `'a'..'f'`
+/// and `range(0, 1000, step = 50)`, otherwise a single value.
+pub(crate) fn expr_to_values(expr: &Expr) -> syn::Result<Vec<ValueWithSpan>> {
+    let (start, end, inclusive, step) = match expr {
+        Expr::Range(range) => {
+            let (Some(start), Some(end)) = (&range.start, &range.end) else {
+                return Err(syn::Error::new_spanned(range, "Expected a range with a start and an end, e.g. `1..=10`"));
+            };
+            let inclusive = matches!(range.limits, syn::RangeLimits::Closed(_));
+            (range_bound(start)?, range_bound(end)?, inclusive, 1)
+        }
+        Expr::Call(call) if matches!(&*call.func, Expr::Path(p) if p.path.is_ident("range")) => {
+            let mut args = call.args.iter();
+            let (Some(start), Some(end)) = (args.next(), args.next()) else {
+                return Err(syn::Error::new_spanned(call, "Expected `range(start, end)` or `range(start, end, step = n)`"));
+            };
+            let step = match args.next() {
+                Some(Expr::Assign(assign)) if matches!(&*assign.left, Expr::Path(p) if p.path.is_ident("step")) => {
+                    match range_bound(&assign.right)? {
+                        RangeBound::Int(0) => return Err(syn::Error::new_spanned(&assign.right, "Expected a non-zero step")),
+                        RangeBound::Int(step) => step,
+                        RangeBound::Char(_) => return Err(syn::Error::new_spanned(&assign.right, "Expected an integer step")),
+                    }
+                }
+                None => 1,
+                Some(other) => return Err(syn::Error::new_spanned(other, "Expected `step = n`")),
+            };
+            if let Some(extra) = args.next() {
+                return Err(syn::Error::new_spanned(extra, "Expected `range(start, end)` or `range(start, end, step = n)`"));
+            }
+            // Like `start..end`, the end is excluded; a negative step counts down
+            (range_bound(start)?, range_bound(end)?, false, step)
+        }
+        _ => return expr_to_value_with_span(expr).map(|value| vec![value]),
+    };
+
+    let (first, mut last) = match (start, end) {
+        (RangeBound::Int(first), RangeBound::Int(last)) => (first, last),
+        (RangeBound::Char(first), RangeBound::Char(last)) => (first as i128, last as i128),
+        _ => return Err(syn::Error::new_spanned(expr, "Both ends of a range must be integers or characters")),
+    };
+    if !inclusive {
+        last -= step.signum();
+    }
+    let count = if (last - first).signum() == -step.signum() { 0 } else { (last - first) / step + 1 };
+    if count <= 0 {
+        return Err(syn::Error::new_spanned(expr, "The range is empty"));
+    }
+    if count > MAX_EXPANDED_CASES as i128 {
+        return Err(syn::Error::new_spanned(
+            expr,
+            format!(
+                "The range expands into {} test cases, more than the limit of {}; use a larger step",
+                count, MAX_EXPANDED_CASES
+            ),
+        ));
+    }
+
+    let mut values = Vec::with_capacity(count as usize);
+    for i in 0..count {
+        let element = first + i * step;
+        let value = match start {
+            RangeBound::Char(_) => match char::from_u32(element as u32) {
+                Some(c) => Value::String(c.to_string()),
+                // Surrogates aren't characters
+                None => continue,
+            },
+            RangeBound::Int(_) => int_to_value(element),
+        };
+        values.push(ValueWithSpan {
+            suffix: Some(value_to_suffix(&value)),
+            value,
+            span: expr.span(),
+            expected: None,
+            ignore: None,
+            should_panic: None,
+            raw: None,
+            constants: Vec::new(),
+            expected_constant: None,
+            csv_row: None,
+        });
+    }
+    Ok(values)
+}
+
+/// An end of a range given to `expr_to_values`.
+#[derive(Clone, Copy)]
+enum RangeBound {
+    Int(i128),
+    Char(char),
+}
+
+fn range_bound(expr: &Expr) -> syn::Result<RangeBound> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: Lit::Int(i), .. }) => {
+            Ok(RangeBound::Int(i.base10_parse::<i128>()?))
+        }
+        Expr::Lit(syn::ExprLit { lit: Lit::Char(c), .. }) => Ok(RangeBound::Char(c.value())),
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr: inner, .. }) => match range_bound(inner)? {
+            RangeBound::Int(n) => Ok(RangeBound::Int(-n)),
+            RangeBound::Char(_) => Err(syn::Error::new_spanned(expr, "Expected an integer or a character literal")),
+        },
+        Expr::Paren(paren) => range_bound(&paren.expr),
+        _ => Err(syn::Error::new_spanned(expr, "Expected an integer or a character literal")),
+    }
+}
+
 #[allow(dead_code)]
 pub fn expr_to_value(expr: &Expr) -> syn::Result<Value> {
     expr_to_value_and_suffix(expr).map(|(v, _)| v)
@@ -233,6 +600,8 @@ pub fn expr_to_value_and_suffix(expr: &Expr) -> syn::Result<(Value, String)> {
             let suffix = value_to_suffix(&val);
             Ok((val, suffix))
         }
+        // Only a whole argument can be a constant, see `expr_to_value_with_span`
+        _ if is_constant_expr(expr) => Err(constant_error(expr)),
         Expr::Path(expr_path) => {
             // Treat path as enum unit variant
             if let Some(segment) = expr_path.path.segments.last() {
@@ -275,6 +644,8 @@ pub fn expr_to_value_and_suffix(expr: &Expr) -> syn::Result<(Value, String)> {
                 return Ok((args[0].clone(), arg_suffixes[0].clone()));
             }
 
+            // A bare name can also be a tuple struct, `Meters(5)`, which `unwrap_tuple_structs`
+            // tells apart by the parameter type
             let value = to_tagged_object(variant_name.clone(), args);
             let suffix = format!("{}_{}", variant_name.to_lowercase(), arg_suffixes.join("_"));
             Ok((value, suffix))
@@ -310,8 +681,14 @@ pub fn expr_to_value_and_suffix(expr: &Expr) -> syn::Result<(Value, String)> {
                 let (val, s) = expr_to_value_and_suffix(&expr_unary.expr)?;
                 match val {
                     Value::Number(n) => {
-                        let negated_val = if let Some(i) = n.as_i64() {
-                            Value::Number((-i).into())
+                        let negated_val = if let Some(i) = n.as_i128() {
+                            int_to_value(-i)
+                        } else if let Some(u) = n.as_u128() {
+                            // Only `i128::MIN` has a magnitude beyond `i128::MAX`
+                            if u != i128::MIN.unsigned_abs() {
+                                return Err(syn::Error::new_spanned(expr, "The negated number does not fit into an i128"));
+                            }
+                            int_to_value(i128::MIN)
                         } else if let Some(f) = n.as_f64() {
                             if let Some(neg_f) = serde_json::Number::from_f64(-f) {
                                 Value::Number(neg_f)
@@ -329,13 +706,73 @@ pub fn expr_to_value_and_suffix(expr: &Expr) -> syn::Result<(Value, String)> {
                 Err(syn::Error::new_spanned(expr, "Unsupported unary operator"))
             }
         }
+        Expr::Array(array) => seq_to_value_and_suffix(array.elems.iter()),
+        Expr::Tuple(tuple) => seq_to_value_and_suffix(tuple.elems.iter()),
+        Expr::Repeat(repeat) => {
+            let len = repeat_len(&repeat.len)?;
+            seq_to_value_and_suffix(std::iter::repeat(&*repeat.expr).take(len))
+        }
+        Expr::Macro(expr_macro) if expr_macro.mac.path.is_ident("vec") => {
+            let elems = vec_macro_elems(&expr_macro.mac)?;
+            seq_to_value_and_suffix(elems.iter())
+        }
+        // `&"text"` and `&[1, 2]` are passed the same way as the values they borrow
+        Expr::Reference(reference) => expr_to_value_and_suffix(&reference.expr),
+        Expr::Paren(paren) => expr_to_value_and_suffix(&paren.expr),
+        Expr::Group(group) => expr_to_value_and_suffix(&group.expr),
         _ => Err(syn::Error::new_spanned(
             expr,
-            "Unsupported expression type. Use literals, enum variants or struct initializers.",
+            "Unsupported expression type. Use literals, arrays, tuples, enum variants or struct initializers.",
         )),
     }
 }
 
+/// Converts the elements of an array, tuple or `vec![]` into a JSON array.
+fn seq_to_value_and_suffix<'a>(elems: impl IntoIterator<Item = &'a Expr>) -> syn::Result<(Value, String)> {
+    let mut values = Vec::new();
+    let mut suffixes = Vec::new();
+    for elem in elems {
+        let (v, s) = expr_to_value_and_suffix(elem)?;
+        values.push(v);
+        suffixes.push(s);
+    }
+    let suffix = if suffixes.is_empty() { "empty".to_string() } else { suffixes.join("_") };
+    Ok((Value::Array(values), suffix))
+}
+
+/// The length of `[value; len]` or `vec![value; len]`, which must be an integer literal.
+fn repeat_len(len: &Expr) -> syn::Result<usize> {
+    let Expr::Lit(syn::ExprLit { lit: Lit::Int(i), .. }) = len else {
+        return Err(syn::Error::new_spanned(len, "Expected an integer literal as the length"));
+    };
+    let len = i.base10_parse::<usize>()?;
+    if len > MAX_EXPANDED_CASES {
+        return Err(syn::Error::new_spanned(
+            i,
+            format!("Expected a length of at most {}", MAX_EXPANDED_CASES),
+        ));
+    }
+    Ok(len)
+}
+
+/// The elements of `vec![a, b, c]` or `vec![value; len]`.
+fn vec_macro_elems(mac: &syn::Macro) -> syn::Result<Vec<Expr>> {
+    use syn::parse::Parser;
+    use syn::punctuated::Punctuated;
+
+    let repeat = |input: syn::parse::ParseStream| -> syn::Result<(Expr, Expr)> {
+        let value: Expr = input.parse()?;
+        input.parse::<syn::Token![;]>()?;
+        Ok((value, input.parse()?))
+    };
+    if let Ok((value, len)) = repeat.parse2(mac.tokens.clone()) {
+        return Ok(vec![value; repeat_len(&len)?]);
+    }
+    Punctuated::<Expr, syn::Token![,]>::parse_terminated
+        .parse2(mac.tokens.clone())
+        .map(|elems| elems.into_iter().collect())
+}
+
 fn to_tagged_object(variant_name: String, args: Vec<Value>) -> Value {
     let mut map = serde_json::Map::new();
     if args.len() == 1 {
@@ -349,9 +786,24 @@ fn to_tagged_object(variant_name: String, args: Vec<Value>) -> Value {
 pub fn lit_to_value(lit: &Lit) -> syn::Result<Value> {
     match lit {
         Lit::Str(s) => Ok(Value::String(s.value())),
+        Lit::Char(c) => Ok(Value::String(c.value().to_string())),
+        Lit::Byte(b) => Ok(Value::from(b.value())),
+        Lit::ByteStr(b) => Ok(Value::Array(b.value().into_iter().map(Value::from).collect())),
         Lit::Int(i) => {
-            let n = i.base10_parse::<i64>().map_err(|e| syn::Error::new(i.span(), e))?;
-            Ok(Value::Number(n.into()))
+            let digits = i.base10_digits();
+            // The literal may be negated afterwards, as in `-128i8`
+            let fits = int_fits(digits, i.suffix()) || int_fits(&format!("-{}", digits), i.suffix());
+            if !i.suffix().is_empty() && !fits {
+                return Err(syn::Error::new(i.span(), format!("Value {} is out of range for type {}", digits, i.suffix())));
+            }
+            match digits.parse::<i128>() {
+                Ok(n) => Ok(int_to_value(n)),
+                // Beyond `i128::MAX`, only valid for `u128` or as the magnitude of `i128::MIN`
+                Err(_) => {
+                    let n = digits.parse::<u128>().map_err(|e| syn::Error::new(i.span(), e))?;
+                    Ok(Value::Number(serde_json::Number::from_u128(n).expect("integers of any size fit into a number")))
+                }
+            }
         }
         Lit::Float(f) => {
             let n = f.base10_parse::<f64>().map_err(|e| syn::Error::new(f.span(), e))?;
@@ -407,53 +859,354 @@ pub fn value_to_suffix(value: &Value) -> String {
     }
 }
 
-pub fn generate_test_set(
-    mut input_fn: ItemFn,
-    json_array: Vec<ValueWithSpan>,
-    fn_name: Ident,
-    type_name: Option<Type>,
-) -> syn::Result<TokenStream> {
+/// Returns the name of the generated tests and the name of the renamed original function.
+/// A function that was already renamed by a previous attribute keeps its name.
+pub(crate) fn impl_fn_names(fn_name: &Ident) -> (Ident, Ident) {
     let fn_name_str = fn_name.to_string();
-    let (real_fn_name, impl_fn_name) = if fn_name_str.starts_with("__") && fn_name_str.ends_with("_impl") && fn_name_str.len() > 7 {
+    if fn_name_str.starts_with("__") && fn_name_str.ends_with("_impl") && fn_name_str.len() > 7 {
         let base = &fn_name_str[2..fn_name_str.len() - 5];
         (format_ident!("{}", base), fn_name.clone())
     } else {
         (fn_name.clone(), format_ident!("__{}_impl", fn_name))
-    };
+    }
+}
 
-    input_fn.sig.ident = impl_fn_name.clone();
+/// The type a test case is deserialized into.
+pub(crate) struct CaseType {
+    pub tokens: TokenStream,
+    /// Whether each argument is a slice parameter `&[T]`, deserialized into a `Vec<T>` and borrowed.
+    pub borrowed: Vec<bool>,
+    /// Type each argument is deserialized into, unless the type is given explicitly.
+    pub args: Vec<TokenStream>,
+}
 
-    let is_tuple = input_fn.sig.inputs.len() > 1;
-    let type_token = if let Some(tn) = type_name {
-        quote!(#tn)
-    } else if is_tuple {
-        let types = input_fn.sig.inputs.iter().map(|arg| {
-            if let syn::FnArg::Typed(pat_type) = arg {
-                let ty = &pat_type.ty;
-                quote!(#ty)
+/// Returns the type a test case is deserialized into: the explicit type if given,
+/// a tuple of the parameter types for multiple parameters, or the type of the only parameter.
+pub(crate) fn case_type_token(input_fn: &ItemFn, type_name: Option<Type>) -> syn::Result<CaseType> {
+    if let Some(tn) = type_name {
+        return Ok(CaseType { tokens: quote!(#tn), borrowed: vec![false; input_fn.sig.inputs.len()], args: Vec::new() });
+    }
+    let mut types = Vec::new();
+    let mut borrowed = Vec::new();
+    for arg in &input_fn.sig.inputs {
+        if let syn::FnArg::Typed(pat_type) = arg {
+            // Slices can't be borrowed from JSON text
+            let slice_elem = match &*pat_type.ty {
+                Type::Reference(reference) => match &*reference.elem {
+                    Type::Slice(slice) => Some(&slice.elem),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(elem) = slice_elem {
+                types.push(quote!(Vec<#elem>));
             } else {
-                quote!(())
+                let ty = &pat_type.ty;
+                types.push(quote!(#ty));
             }
-        });
-        quote!((#(#types),*))
-    } else if input_fn.sig.inputs.len() == 1 {
-        if let Some(syn::FnArg::Typed(pat_type)) = input_fn.sig.inputs.first() {
-            let ty = &pat_type.ty;
-            quote!(#ty)
+            borrowed.push(slice_elem.is_some());
         } else {
-            return Err(syn::Error::new_spanned(
-                &input_fn.sig.inputs,
-                "Could not infer type for test case. Please provide it explicitly.",
-            ));
+            types.push(quote!(()));
+            borrowed.push(false);
         }
-    } else {
-        return Err(syn::Error::new_spanned(
+    }
+    match input_fn.sig.inputs.first() {
+        Some(syn::FnArg::Typed(_)) if types.len() == 1 => {
+            Ok(CaseType { tokens: types[0].clone(), borrowed, args: types })
+        }
+        Some(_) if types.len() > 1 => Ok(CaseType { tokens: quote!((#(#types),*)), borrowed, args: types }),
+        _ => Err(syn::Error::new_spanned(
             &input_fn.sig.inputs,
             "Could not infer type for test case. Please provide it explicitly.",
-        ));
+        )),
+    }
+}
+
+/// How a generated test runs the test function.
+pub(crate) enum Runner {
+    /// A `#[test]` calling a synchronous test function.
+    Sync,
+    /// An async test with the test attribute of an async runtime, e.g. `#[tokio::test]`.
+    Runtime(Box<syn::Meta>),
+    /// A `#[test]` running the async test function with `runtime::block_on`.
+    BlockOn,
+}
+
+impl Runner {
+    /// Returns how the tests of `input_fn` are run, with the `runtime` attribute given for async tests.
+    pub(crate) fn new(input_fn: &ItemFn, runtime: Option<syn::Meta>) -> syn::Result<Self> {
+        match (input_fn.sig.asyncness, runtime) {
+            (None, Some(runtime)) => Err(syn::Error::new_spanned(runtime, "`runtime` requires an async test function")),
+            (None, None) => Ok(Runner::Sync),
+            (Some(_), Some(runtime)) => Ok(Runner::Runtime(Box::new(runtime))),
+            (Some(_), None) => Ok(Runner::BlockOn),
+        }
+    }
+
+    /// `.await` for the call of an async test function.
+    pub(crate) fn await_token(&self) -> Option<TokenStream> {
+        (!matches!(self, Runner::Sync)).then(|| quote!(.await))
+    }
+
+    /// Runs statements awaiting the test function to completion, for a test calling it once per case.
+    pub(crate) fn block_on(&self, statements: TokenStream) -> TokenStream {
+        match self {
+            Runner::Sync => statements,
+            _ => quote! {
+                rust_test_framework::__private::runtime::block_on(async { #statements });
+            },
+        }
+    }
+
+    /// Generates a test named `test_fn_name` with the attributes `attrs` running `body`.
+    fn test_fn(&self, test_fn_name: &Ident, attrs: TokenStream, body: TokenStream) -> TokenStream {
+        match self {
+            Runner::Sync => quote! {
+                #[test]
+                #attrs
+                fn #test_fn_name() {
+                    #body
+                }
+            },
+            Runner::Runtime(runtime) => quote! {
+                #[#runtime]
+                #attrs
+                async fn #test_fn_name() {
+                    #body
+                }
+            },
+            Runner::BlockOn => quote! {
+                #[test]
+                #attrs
+                fn #test_fn_name() {
+                    rust_test_framework::__private::runtime::block_on(async {
+                        #body
+                    });
+                }
+            },
+        }
+    }
+}
+
+/// Parses the value of the `runtime` option: the test attribute of an async runtime, e.g. `"tokio::test"`.
+pub(crate) fn parse_runtime(input: ParseStream) -> syn::Result<syn::Meta> {
+    let lit: LitStr = input.parse()?;
+    lit.parse().map_err(|_| {
+        syn::Error::new_spanned(&lit, "Expected the test attribute of an async runtime, e.g. `\"tokio::test\"`")
+    })
+}
+
+/// Records the `runtime` given to an attribute on the test function, where `generate_test_set` reads it.
+/// Attributes stacked below inherit it.
+pub(crate) fn set_runtime(input_fn: &mut ItemFn, runtime: Option<syn::Meta>) {
+    if let Some(runtime) = runtime {
+        input_fn.attrs.push(syn::parse_quote!(#[rust_test_framework::rust_test_runtime(#runtime)]));
+    }
+}
+
+/// The modules whose `#[before_all]` and `#[after_all]` functions the generated tests enter, which
+/// `#[test_fixture]` passes with a `rust_test_enter` marker right after each attribute generating tests,
+/// along with the constant the attribute defines with the number of its tests.
+pub(crate) struct ModuleEnter {
+    count: Ident,
+    modules: Vec<syn::Path>,
+}
+
+impl ModuleEnter {
+    /// Takes the marker of the attribute being expanded off `input_fn`, if the test is in such a fixture.
+    /// The markers of the attributes stacked below it follow, so it's the first one.
+    pub(crate) fn take(input_fn: &mut ItemFn) -> syn::Result<Option<Self>> {
+        let Some(index) = input_fn
+            .attrs
+            .iter()
+            .position(|attr| attr.path().segments.last().is_some_and(|s| s.ident == "rust_test_enter"))
+        else {
+            return Ok(None);
+        };
+        let attr = input_fn.attrs.remove(index);
+        let mut paths = attr
+            .parse_args_with(syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)?
+            .into_iter();
+        let count = paths
+            .next()
+            .and_then(|path| path.get_ident().cloned())
+            .ok_or_else(|| syn::Error::new_spanned(&attr, "Expected the name of the test count"))?;
+        Ok(Some(ModuleEnter { count, modules: paths.collect() }))
+    }
+
+    /// Statements entering the modules at the start of a generated test, which leaves them when it ends.
+    pub(crate) fn statements(&self) -> TokenStream {
+        let guards = (0..self.modules.len()).map(|i| format_ident!("__rust_test_module_{}", i));
+        let modules = &self.modules;
+        quote! {
+            #(let #guards = #modules::__rust_test_module_hooks();)*
+        }
+    }
+
+    /// Defines the constant with the number of the generated tests that run by default.
+    pub(crate) fn count(&self, tests: usize) -> TokenStream {
+        let count = &self.count;
+        quote! {
+            #[doc(hidden)]
+            const #count: usize = #tests;
+        }
+    }
+}
+
+/// Generates the statements that deserialize a test case from `json` and call the test function with it.
+/// If `csv_row` is given, the case is a CSV row deserialized into the fields of a struct, see [`ValueWithSpan::csv_row`].
+/// If `check` is given, the returned value is bound to `actual` before running it.
+pub(crate) fn deserialize_and_call(
+    impl_fn_name: &Ident,
+    json: TokenStream,
+    csv_row: Option<TokenStream>,
+    case_type: &CaseType,
+    arg_count: usize,
+    check: Option<TokenStream>,
+    runner: &Runner,
+) -> TokenStream {
+    let await_token = runner.await_token();
+    let type_token = &case_type.tokens;
+    let borrow = |i: usize| case_type.borrowed.get(i).copied().unwrap_or(false).then(|| quote!(&));
+    let (deserialize, call) = if arg_count > 1 {
+        let idents: Vec<_> = (0..arg_count)
+            .map(|i| format_ident!("arg_{}", i))
+            .collect();
+        let args = idents.iter().enumerate().map(|(i, ident)| {
+            let borrow = borrow(i);
+            quote!(#borrow #ident)
+        });
+        (
+            quote! {
+                let (#(#idents),*): #type_token = rust_test_framework::__private::serde_json::from_str(#json).unwrap();
+            },
+            quote!(#impl_fn_name(#(#args),*) #await_token),
+        )
+    } else {
+        let borrow = borrow(0);
+        let deserialize = match csv_row {
+            Some(row) => quote!(rust_test_framework::__private::runtime::from_csv_row(#json, #row)),
+            None => quote!(rust_test_framework::__private::serde_json::from_str(#json).unwrap()),
+        };
+        (
+            quote! {
+                let data: #type_token = #deserialize;
+            },
+            quote!(#impl_fn_name(#borrow data) #await_token),
+        )
     };
 
+    if let Some(check) = check {
+        quote! {
+            #deserialize
+            let actual = #call;
+            #check
+        }
+    } else {
+        quote! {
+            #deserialize
+            #call;
+        }
+    }
+}
+
+/// Generates the statements that deserialize the expected value from `json`
+/// and compare it with the `actual` value returned by the test function.
+pub(crate) fn compare_expected(json: TokenStream, return_type: &Type) -> TokenStream {
+    quote! {
+        let expected: #return_type = rust_test_framework::__private::serde_json::from_str(#json).unwrap();
+        rust_test_framework::__private::runtime::assert_expected(&actual, &expected);
+    }
+}
+
+/// Generates the statements that fail the test case if the `actual` value returned by the test function
+/// is an `Err`, or implements `Termination` and reports a failure as `#[test]` does. Other values are ignored.
+pub(crate) fn assert_success(input: TokenStream) -> TokenStream {
+    quote! {
+        {
+            use rust_test_framework::__private::runtime::{CheckResult as _, CheckTermination as _, IgnoreReturned as _};
+            (&&&rust_test_framework::__private::runtime::Returned::new(actual)).assert_success(#input);
+        }
+    }
+}
+
+/// Returns the type of the value returned by a function, if it returns one.
+pub(crate) fn return_type(input_fn: &ItemFn) -> Option<&Type> {
+    match &input_fn.sig.output {
+        syn::ReturnType::Type(_, ty) if !matches!(&**ty, Type::Tuple(t) if t.elems.is_empty()) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Separates the `$ignore` and `$should_panic` markers from a test case read from a data source.
+///
+/// A marker is either a string (the reason or the expected panic message) or a boolean.
+/// If only an `input` field remains, its value is the test case.
+pub(crate) fn split_markers(value: Value) -> Result<(Value, Option<String>, Option<String>), String> {
+    let mut object = match value {
+        Value::Object(object) if object.keys().any(|k| k.starts_with('$')) => object,
+        other => return Ok((other, None, None)),
+    };
+
+    let mut ignore = None;
+    let mut should_panic = None;
+    for key in object.keys().filter(|k| k.starts_with('$')).cloned().collect::<Vec<_>>() {
+        let marker = match object.remove(&key).unwrap() {
+            Value::String(s) => Some(s),
+            Value::Bool(true) => Some(String::new()),
+            Value::Bool(false) => None,
+            other => {
+                return Err(format!("Expected a string or a boolean for `{}`, but got: {}", key, other));
+            }
+        };
+        match key.as_str() {
+            "$ignore" => ignore = marker,
+            "$should_panic" => should_panic = marker,
+            _ => return Err(format!("Unknown test case key `{}`, expected `$ignore` or `$should_panic`", key)),
+        }
+    }
+
+    if object.len() == 1 && object.contains_key("input") {
+        Ok((object.remove("input").unwrap(), ignore, should_panic))
+    } else {
+        Ok((Value::Object(object), ignore, should_panic))
+    }
+}
+
+/// Separates the expected result from the input of a test case read from a data source:
+/// either an `{"input": ..., "expected": ...}` envelope or an object with an `expected` field.
+pub(crate) fn split_expected(value: Value) -> (Value, Option<Value>) {
+    match value {
+        Value::Object(mut object) if object.contains_key("expected") => {
+            let expected = object.remove("expected");
+            if object.len() == 1 && object.contains_key("input") {
+                (object.remove("input").unwrap(), expected)
+            } else {
+                (Value::Object(object), expected)
+            }
+        }
+        other => (other, None),
+    }
+}
+
+pub fn generate_test_set(
+    mut input_fn: ItemFn,
+    json_array: Vec<ValueWithSpan>,
+    fn_name: Ident,
+    type_name: Option<Type>,
+) -> syn::Result<TokenStream> {
+    let (real_fn_name, impl_fn_name) = impl_fn_names(&fn_name);
+
+    input_fn.sig.ident = impl_fn_name.clone();
+    let enter = ModuleEnter::take(&mut input_fn)?;
+
+    let is_tuple = input_fn.sig.inputs.len() > 1;
+    let case_type = case_type_token(&input_fn, type_name)?;
+
     let mut seen_values = Vec::new();
+    let mut seen_exprs = Vec::new();
+    let mut seen_names = Vec::new();
+    let mut runtime = None;
     let mut other_attrs = Vec::new();
     for attr in input_fn.attrs {
         if attr.path().segments.last().map_or(false, |s| s.ident == "rust_test_seen_value") {
@@ -462,27 +1215,93 @@ pub fn generate_test_set(
                     seen_values.extend(prev_values);
                 }
             }
+        } else if attr.path().segments.last().is_some_and(|s| s.ident == "rust_test_seen_expr") {
+            if let Ok(nested) = attr.parse_args::<LitStr>() {
+                if let Ok(prev_exprs) = serde_json::from_str::<Vec<String>>(&nested.value()) {
+                    seen_exprs.extend(prev_exprs);
+                }
+            }
+        } else if attr.path().segments.last().is_some_and(|s| s.ident == "rust_test_seen_name") {
+            if let Ok(nested) = attr.parse_args::<LitStr>() {
+                if let Ok(prev_names) = serde_json::from_str::<Vec<String>>(&nested.value()) {
+                    seen_names.extend(prev_names);
+                }
+            }
+        } else if attr.path().segments.last().is_some_and(|s| s.ident == "rust_test_runtime") {
+            runtime = Some(attr.parse_args::<syn::Meta>()?);
         } else {
             other_attrs.push(attr);
         }
     }
     input_fn.attrs = other_attrs;
+    let runner = Runner::new(&input_fn, runtime.clone())?;
+    let set = TestSet {
+        fn_name: &real_fn_name,
+        impl_fn_name: &impl_fn_name,
+        case_type: &case_type,
+        is_tuple,
+        arg_count: input_fn.sig.inputs.len(),
+        return_type: return_type(&input_fn),
+        runner: &runner,
+        enter: enter.as_ref().map(ModuleEnter::statements).unwrap_or_default(),
+    };
 
-    let test_functions = if json_array.len() == 1 {
-        let value_with_span = &json_array[0];
+    let single = json_array.len() == 1;
+    let mut tests = Vec::with_capacity(json_array.len());
+    for (i, value_with_span) in json_array.iter().enumerate() {
         let value = &value_with_span.value;
+        let index = if single { None } else { Some(i) };
 
-        // Check for duplicate values
-        if seen_values.contains(value) {
+        // Check for duplicate values, or duplicate expressions of `#[test_case]`s and constants
+        let exprs = match &value_with_span.raw {
+            Some(raw) => Some(raw.args_string()),
+            None if value_with_span.constants.iter().any(Option::is_some) => {
+                Some(describe_constants(value_with_span, is_tuple))
+            }
+            None => None,
+        };
+        let duplicate = match exprs {
+            Some(exprs) => {
+                let duplicate = seen_exprs.contains(&exprs);
+                seen_exprs.push(exprs);
+                duplicate
+            }
+            None => {
+                let duplicate = seen_values.contains(value);
+                seen_values.push(value.clone());
+                duplicate
+            }
+        };
+        if duplicate {
             return Err(syn::Error::new(
                 value_with_span.span,
                 "Duplicate test case value found",
             ));
         }
-        seen_values.push(value.clone());
+
+        // Check for duplicate names, which different values can still produce
+        let test_fn_name = match (&value_with_span.raw, &value_with_span.suffix) {
+            // Unnamed `#[test_case]`s are numbered in the order of the attributes
+            (Some(_), None) => (1usize..)
+                .map(|n| format_ident!("{}__case_{}", real_fn_name, n))
+                .find(|name| !seen_names.contains(&name.to_string()))
+                .unwrap(),
+            _ => test_fn_name(&real_fn_name, value_with_span, index),
+        };
+        let test_fn_name_str = test_fn_name.to_string();
+        if seen_names.contains(&test_fn_name_str) {
+            return Err(syn::Error::new(
+                value_with_span.span,
+                format!(
+                    "Duplicate test name `{}`, use `name = \"...\"` to give the test case a unique name",
+                    test_fn_name_str
+                ),
+            ));
+        }
+        seen_names.push(test_fn_name_str);
 
         // Check for Path existence if applicable
-        if input_fn.sig.inputs.len() == 1 {
+        if input_fn.sig.inputs.len() == 1 && value_with_span.raw.is_none() {
             if let Some(syn::FnArg::Typed(pat_type)) = input_fn.sig.inputs.first() {
                 if is_path_type(&pat_type.ty) {
                     if let Value::String(path_str) = value {
@@ -497,66 +1316,23 @@ pub fn generate_test_set(
             }
         }
 
-        generate_single_test(
-            &real_fn_name,
-            &impl_fn_name,
-            value_with_span,
-            None,
-            &type_token,
-            is_tuple,
-            input_fn.sig.inputs.len(),
-        )?
-    } else {
-        let tests = json_array
-            .into_iter()
-            .enumerate()
-            .map(|(i, value_with_span)| {
-                let value = &value_with_span.value;
-
-                // Check for duplicate values
-                if seen_values.contains(value) {
-                    return Err(syn::Error::new(
-                        value_with_span.span,
-                        "Duplicate test case value found",
-                    ));
-                }
-                seen_values.push(value.clone());
-
-                // Check for Path existence if applicable
-                if input_fn.sig.inputs.len() == 1 {
-                    if let Some(syn::FnArg::Typed(pat_type)) = input_fn.sig.inputs.first() {
-                        if is_path_type(&pat_type.ty) {
-                            if let Value::String(path_str) = &value_with_span.value {
-                                if resolve_path(path_str).is_none() {
-                                    return Err(syn::Error::new(
-                                        value_with_span.span,
-                                        format!("Path not found: {}", path_str),
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                }
-
-                generate_single_test(
-                    &real_fn_name,
-                    &impl_fn_name,
-                    &value_with_span,
-                    Some(i),
-                    &type_token,
-                    is_tuple,
-                    input_fn.sig.inputs.len(),
-                )
-            })
-            .collect::<syn::Result<Vec<_>>>()?;
-
-        quote! {
-            #(#tests)*
-        }
+        tests.push(generate_single_test(&set, &test_fn_name, value_with_span, index)?);
+    }
+    let test_count = enter.map(|enter| enter.count(json_array.iter().filter(|case| case.ignore.is_none()).count()));
+    let test_functions = quote! {
+        #test_count
+        #(#tests)*
     };
 
     let seen_values_json = serde_json::to_string(&seen_values).unwrap_or_default();
     input_fn.attrs.push(syn::parse_quote!(#[rust_test_framework::rust_test_seen_value(#seen_values_json)]));
+    if !seen_exprs.is_empty() {
+        let seen_exprs_json = serde_json::to_string(&seen_exprs).unwrap_or_default();
+        input_fn.attrs.push(syn::parse_quote!(#[rust_test_framework::rust_test_seen_expr(#seen_exprs_json)]));
+    }
+    let seen_names_json = serde_json::to_string(&seen_names).unwrap_or_default();
+    input_fn.attrs.push(syn::parse_quote!(#[rust_test_framework::rust_test_seen_name(#seen_names_json)]));
+    set_runtime(&mut input_fn, runtime);
 
     Ok(quote! {
         /// Original test function
@@ -565,15 +1341,79 @@ pub fn generate_test_set(
     })
 }
 
+/// Returns the `#[ignore]` and `#[should_panic]` attributes of a test case.
+fn case_markers(value_with_span: &ValueWithSpan) -> TokenStream {
+    let ignore = match value_with_span.ignore.as_deref() {
+        Some("") => quote!(#[ignore]),
+        Some(reason) => quote!(#[ignore = #reason]),
+        None => quote!(),
+    };
+    let should_panic = match value_with_span.should_panic.as_deref() {
+        Some("") => quote!(#[should_panic]),
+        Some(expected) => quote!(#[should_panic(expected = #expected)]),
+        None => quote!(),
+    };
+    quote! {
+        #ignore
+        #should_panic
+    }
+}
+
+/// Returns the name of the test generated for a test case: `{fn_name}__{suffix}`,
+/// or `{fn_name}_{index}` if the case has no suffix.
+fn test_fn_name(fn_name: &Ident, value_with_span: &ValueWithSpan, index: Option<usize>) -> Ident {
+    let suffix = if let Some(s) = &value_with_span.suffix {
+        s.clone()
+    } else {
+        value_to_suffix(&value_with_span.value)
+    };
+    if suffix.is_empty() {
+        if let Some(i) = index {
+            format_ident!("{}_{}", fn_name, i)
+        } else {
+            fn_name.clone()
+        }
+    } else {
+        format_ident!("{}__{}", fn_name, suffix)
+    }
+}
+
+/// Checks that a test case name given with `name = "..."` gives a valid test name.
+pub(crate) fn validate_test_name(fn_name: &Ident, name: &LitStr) -> syn::Result<()> {
+    let (real_fn_name, _) = impl_fn_names(fn_name);
+    if name.value().is_empty() || syn::parse_str::<Ident>(&format!("{}__{}", real_fn_name, name.value())).is_err() {
+        return Err(syn::Error::new_spanned(
+            name,
+            format!("Test case name `{}` must consist of letters, digits and underscores", name.value()),
+        ));
+    }
+    Ok(())
+}
+
+/// What the tests generated for the cases of a single test function have in common.
+struct TestSet<'a> {
+    fn_name: &'a Ident,
+    impl_fn_name: &'a Ident,
+    case_type: &'a CaseType,
+    is_tuple: bool,
+    arg_count: usize,
+    return_type: Option<&'a Type>,
+    runner: &'a Runner,
+    /// Statements entering the `#[before_all]` hooks of the enclosing fixtures, see [`ModuleEnter`].
+    enter: TokenStream,
+}
+
 fn generate_single_test(
-    fn_name: &Ident,
-    impl_fn_name: &Ident,
+    set: &TestSet,
+    test_fn_name: &Ident,
     value_with_span: &ValueWithSpan,
     index: Option<usize>,
-    type_token: &TokenStream,
-    is_tuple: bool,
-    arg_count: usize,
 ) -> syn::Result<TokenStream> {
+    if let Some(raw) = &value_with_span.raw {
+        return Ok(generate_raw_test(set, test_fn_name, value_with_span, raw));
+    }
+    let TestSet { fn_name, impl_fn_name, case_type, is_tuple, arg_count, return_type, runner, ref enter } = *set;
+
     let value = &value_with_span.value;
     let json_str = serialize_json(value).map_err(|e| {
         let msg = if let Some(i) = index {
@@ -584,48 +1424,152 @@ fn generate_single_test(
         syn::Error::new_spanned(fn_name, msg)
     })?;
 
-    let suffix = if let Some(s) = &value_with_span.suffix {
-        s.clone()
+    let docstring = if let Some(i) = index {
+        format!("Generated test {} #{}", fn_name, i)
     } else {
-        value_to_suffix(value)
+        format!("Generated test {}", fn_name)
     };
-    let test_fn_name = if suffix.is_empty() {
-        if let Some(i) = index {
-            format_ident!("{}_{}", fn_name, i)
-        } else {
-            fn_name.clone()
+
+    let has_constants = value_with_span.constants.iter().any(Option::is_some);
+    let check = match (&value_with_span.expected_constant, &value_with_span.expected, return_type) {
+        (Some(expected), _, Some(return_type)) => Some(quote! {
+            let expected: #return_type = #expected;
+            rust_test_framework::__private::runtime::assert_expected(&actual, &expected);
+        }),
+        (None, Some(expected), Some(return_type)) => {
+            let expected_str = serialize_json(expected).map_err(|e| {
+                syn::Error::new_spanned(fn_name, format!("Failed to serialize expected value: {}", e))
+            })?;
+            Some(compare_expected(quote!(#expected_str), return_type))
         }
-    } else {
-        format_ident!("{}__{}", fn_name, suffix)
+        (None, None, Some(_)) if has_constants => {
+            let input = describe_constants(value_with_span, is_tuple);
+            Some(assert_success(quote!(#input)))
+        }
+        (None, None, Some(_)) => Some(assert_success(quote!(#json_str))),
+        _ => None,
     };
-
-    let docstring = if let Some(i) = index {
-        format!("Generated test {} #{}", fn_name, i)
+    let call_expr = if has_constants {
+        call_with_constants(fn_name, impl_fn_name, value_with_span, case_type, is_tuple, check, runner)?
     } else {
-        format!("Generated test {}", fn_name)
+        let csv_row = value_with_span.csv_row.map(|row| quote!(Some(#row)));
+        deserialize_and_call(impl_fn_name, quote!(#json_str), csv_row, case_type, arg_count, check, runner)
     };
+    let markers = case_markers(value_with_span);
 
-    let call_expr = if is_tuple {
-        let idents: Vec<_> = (0..arg_count)
-            .map(|i| format_ident!("arg_{}", i))
-            .collect();
+    Ok(runner.test_fn(
+        test_fn_name,
+        quote! {
+            #[doc = #docstring]
+            #markers
+            #[allow(non_snake_case)]
+        },
+        quote!(#enter #call_expr),
+    ))
+}
+
+/// The arguments of a test case as a JSON array, or value for a single argument,
+/// with its constants as they are written.
+fn case_args(value_with_span: &ValueWithSpan, is_tuple: bool) -> &[Value] {
+    let value = &value_with_span.value;
+    if is_tuple { value.as_array().map(Vec::as_slice).unwrap_or_default() } else { std::slice::from_ref(value) }
+}
+
+/// Describes the arguments of a test case with constants, which are shown as they are written.
+fn describe_constants(value_with_span: &ValueWithSpan, is_tuple: bool) -> String {
+    let described: Vec<String> = case_args(value_with_span, is_tuple)
+        .iter()
+        .enumerate()
+        .map(|(i, arg)| match value_with_span.constant(i) {
+            Some(expr) => quote!(#expr).to_string(),
+            None => serialize_json(arg).unwrap_or_default(),
+        })
+        .collect();
+    if is_tuple { format!("[{}]", described.join(",")) } else { described.concat() }
+}
+
+/// Generates the statements that call the test function with the constants of a test case as they are,
+/// deserializing each of the other arguments on its own.
+fn call_with_constants(
+    fn_name: &Ident,
+    impl_fn_name: &Ident,
+    value_with_span: &ValueWithSpan,
+    case_type: &CaseType,
+    is_tuple: bool,
+    check: Option<TokenStream>,
+    runner: &Runner,
+) -> syn::Result<TokenStream> {
+    let mut statements = Vec::new();
+    let mut call_args = Vec::new();
+    let span = value_with_span.span;
+    for (i, arg) in case_args(value_with_span, is_tuple).iter().enumerate() {
+        if let Some(expr) = value_with_span.constant(i) {
+            call_args.push(quote!(#expr));
+            continue;
+        }
+        let ty = case_type.args.get(i).ok_or_else(|| {
+            syn::Error::new(span, "Constants can't be used when the type of the test cases is given explicitly")
+        })?;
+        let json_str = serialize_json(arg).map_err(|e| {
+            syn::Error::new_spanned(fn_name, format!("Failed to serialize JSON: {}", e))
+        })?;
+        let ident = format_ident!("arg_{}", i);
+        statements.push(quote! {
+            let #ident: #ty = rust_test_framework::__private::serde_json::from_str(#json_str).unwrap();
+        });
+        let borrow = case_type.borrowed.get(i).copied().unwrap_or(false).then(|| quote!(&));
+        call_args.push(quote!(#borrow #ident));
+    }
+
+    let await_token = runner.await_token();
+    let call = quote!(#impl_fn_name(#(#call_args),*) #await_token);
+    Ok(if let Some(check) = check {
         quote! {
-            let (#(#idents),*): #type_token = rust_test_framework::__private::serde_json::from_str(#json_str).unwrap();
-            #impl_fn_name(#(#idents),*);
+            #(#statements)*
+            let actual = #call;
+            #check
         }
     } else {
         quote! {
-            let data: #type_token = rust_test_framework::__private::serde_json::from_str(#json_str).unwrap();
-            #impl_fn_name(data);
+            #(#statements)*
+            #call;
         }
-    };
+    })
+}
 
-    Ok(quote! {
-        #[doc = #docstring]
-        #[test]
-        #[allow(non_snake_case)]
-        fn #test_fn_name() {
-            #call_expr
+/// Generates a test that calls the test function with the expressions of a `#[test_case]`.
+fn generate_raw_test(set: &TestSet, test_fn_name: &Ident, value_with_span: &ValueWithSpan, raw: &RawCase) -> TokenStream {
+    let TestSet { fn_name, impl_fn_name, return_type, runner, ref enter, .. } = *set;
+    let docstring = format!("Generated test {}", fn_name);
+    let args = &raw.args;
+    let await_token = runner.await_token();
+    let call_expr = match (&raw.expected, return_type) {
+        (Some(expected), Some(return_type)) => quote! {
+            let actual = #impl_fn_name(#(#args),*) #await_token;
+            let expected: #return_type = #expected;
+            rust_test_framework::__private::runtime::assert_expected(&actual, &expected);
+        },
+        (None, Some(_)) => {
+            let input = quote!(#(#args),*).to_string();
+            let check = assert_success(quote!(#input));
+            quote! {
+                let actual = #impl_fn_name(#(#args),*) #await_token;
+                #check
+            }
         }
-    })
+        _ => quote! {
+            #impl_fn_name(#(#args),*) #await_token;
+        },
+    };
+    let markers = case_markers(value_with_span);
+
+    runner.test_fn(
+        test_fn_name,
+        quote! {
+            #[doc = #docstring]
+            #markers
+            #[allow(non_snake_case)]
+        },
+        quote!(#enter #call_expr),
+    )
 }
@@ -0,0 +1,124 @@
+use crate::attributes::common::{
+    generate_test_set, parse_item_fn, parse_runtime, return_type, set_runtime, validate_test_name, RawCase,
+    ValueWithSpan,
+};
+use crate::attributes::test_params::parse_marker;
+use proc_macro2::TokenStream;
+use serde_json::Value;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{parse2, Expr, Ident, LitStr, Token};
+
+pub fn test_case(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let mut input_fn = parse_item_fn(item)?;
+    let fn_name = input_fn.sig.ident.clone();
+
+    let args: TestCaseExprArgs = parse2(attr)?;
+    let arg_count = input_fn.sig.inputs.len();
+
+    if args.values.len() != arg_count {
+        return Err(syn::Error::new_spanned(
+            &input_fn.sig.inputs,
+            format!(
+                "Test function expects {} arguments, but {} were provided in #[test_case]",
+                arg_count,
+                args.values.len()
+            ),
+        ));
+    }
+    if let Some(name) = &args.name {
+        validate_test_name(&fn_name, name)?;
+    }
+    if let Some(expect) = &args.expect {
+        if return_type(&input_fn).is_none() {
+            return Err(syn::Error::new_spanned(
+                expect,
+                "`expect` requires the test function to return a value",
+            ));
+        }
+    }
+
+    let span = args.values.first().map_or_else(|| fn_name.span(), |v| v.span());
+    let case = ValueWithSpan {
+        // The arguments are passed as they are, duplicates are found by comparing their tokens
+        value: Value::Null,
+        span,
+        suffix: args.name.map(|name| name.value()),
+        expected: None,
+        ignore: args.ignore,
+        should_panic: args.should_panic,
+        raw: Some(RawCase {
+            args: args.values,
+            expected: args.expect,
+        }),
+        constants: Vec::new(),
+        expected_constant: None,
+        csv_row: None,
+    };
+
+    set_runtime(&mut input_fn, args.runtime);
+    generate_test_set(input_fn, vec![case], fn_name, None)
+}
+
+/// Arguments of `#[test_case]`: an expression per parameter, followed by options,
+/// e.g. `Duration::from_secs(3), make_user("x"), name = "slow_user"`.
+struct TestCaseExprArgs {
+    values: Vec<Expr>,
+    /// Name of the test case given with `name = "..."`, instead of its number.
+    name: Option<LitStr>,
+    /// Expression the test function must return, given with `expect = ...`.
+    expect: Option<Expr>,
+    /// Reason given with `ignore = "..."`, or empty for `ignore = true`.
+    ignore: Option<String>,
+    /// Expected panic message given with `should_panic = "..."`, or empty for `should_panic = true`.
+    should_panic: Option<String>,
+    /// Test attribute of the async runtime running async tests, given with `runtime = "tokio::test"`.
+    runtime: Option<syn::Meta>,
+}
+
+impl Parse for TestCaseExprArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut values = Vec::new();
+        let mut name = None;
+        let mut expect = None;
+        let mut ignore = None;
+        let mut should_panic = None;
+        let mut runtime = None;
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+                let key: Ident = input.parse()?;
+                input.parse::<Token![=]>()?;
+                match key.to_string().as_str() {
+                    "name" => name = Some(input.parse::<LitStr>()?),
+                    "expect" => expect = Some(input.parse::<Expr>()?),
+                    "ignore" => ignore = parse_marker(input)?,
+                    "should_panic" => should_panic = parse_marker(input)?,
+                    "runtime" => runtime = Some(parse_runtime(input)?),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &key,
+                            format!("Unknown test_case option: {}", key),
+                        ));
+                    }
+                }
+            } else {
+                values.push(input.parse::<Expr>()?);
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(TestCaseExprArgs {
+            values,
+            name,
+            expect,
+            ignore,
+            should_panic,
+            runtime,
+        })
+    }
+}
@@ -1,16 +1,223 @@
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{parse2, ItemFn, ItemMod, Item};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{bracketed, parenthesized, parse2, Expr, FnArg, Ident, ItemFn, ItemMod, Item, Lit, Pat, PatType, Path, ReturnType, Signature, Token, Type, UnOp};
+
+pub fn test_fixture(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let options = Punctuated::<FixtureOption, Token![,]>::parse_terminated.parse2(attr)?;
+    let mut uses = Vec::new();
+    let mut used_hooks = Vec::new();
+    let mut inherited = Vec::new();
+    for option in options {
+        match option {
+            FixtureOption::Uses(paths) => uses.extend(paths),
+            FixtureOption::Hooks(module, hooks) => used_hooks.push((module, hooks)),
+            FixtureOption::Inherit(hooks) => inherited.extend(hooks),
+        }
+    }
 
-pub fn test_fixture(_attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
     if let Ok(mut input_mod) = parse2::<ItemMod>(item.clone()) {
-        process_mod(&mut input_mod)?;
+        // The hooks of a used fixture are only known to a macro it defines, which passes them back
+        // to this attribute, so the module is expanded once per used fixture
+        if !uses.is_empty() {
+            let module = uses.remove(0);
+            let hooks = used_hooks.iter().map(|(module, hooks)| quote!(__hooks([#module], [#(#hooks)*]),));
+            let inherit = (!inherited.is_empty()).then(|| quote!(__inherit([#(#inherited)*]),));
+            return Ok(quote! {
+                #module::__rust_test_fixture! {
+                    [#module] { #(uses = #uses,)* #inherit #(#hooks)* } #input_mod
+                }
+            });
+        }
+        process_mod(&mut input_mod, used_hooks, inherited)?;
         return Ok(quote!(#input_mod));
     }
 
     Err(syn::Error::new_spanned(item, "The `#[test_fixture]` attribute can only be applied to a module."))
 }
 
+enum FixtureOption {
+    /// `uses = path` or `uses = [path, ...]`, fixtures whose setup and teardown functions the module reuses
+    Uses(Vec<Path>),
+    /// `__hooks([path], [...])`, the hooks of a used fixture, given by the macro it defines
+    Hooks(Path, Vec<HookSignature>),
+    /// `__inherit([...])`, the hooks of the enclosing fixture, given by it to its nested modules
+    Inherit(Vec<InheritedHook>),
+}
+
+impl Parse for FixtureOption {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if name == "uses" {
+            input.parse::<Token![=]>()?;
+            if input.peek(syn::token::Bracket) {
+                let content;
+                bracketed!(content in input);
+                let paths = Punctuated::<Path, Token![,]>::parse_terminated(&content)?;
+                return Ok(FixtureOption::Uses(paths.into_iter().collect()));
+            }
+            return Ok(FixtureOption::Uses(vec![input.parse()?]));
+        }
+        if name == "__hooks" {
+            let content;
+            parenthesized!(content in input);
+            let module;
+            bracketed!(module in content);
+            let module = module.parse()?;
+            content.parse::<Token![,]>()?;
+            let hooks;
+            bracketed!(hooks in content);
+            let mut signatures = Vec::new();
+            while !hooks.is_empty() {
+                signatures.push(hooks.parse()?);
+            }
+            return Ok(FixtureOption::Hooks(module, signatures));
+        }
+        if name == "__inherit" {
+            let content;
+            parenthesized!(content in input);
+            let hooks;
+            bracketed!(hooks in content);
+            let mut inherited = Vec::new();
+            while !hooks.is_empty() {
+                inherited.push(hooks.parse()?);
+            }
+            return Ok(FixtureOption::Inherit(inherited));
+        }
+        Err(syn::Error::new(name.span(), format!("Unknown test_fixture option: {}", name)))
+    }
+}
+
+/// Describes a setup or teardown function to the fixtures using its module: `setup(order) fn name() -> Ctx;`
+struct HookSignature {
+    is_setup: bool,
+    order: i64,
+    sig: Signature,
+}
+
+impl Parse for HookSignature {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let kind: Ident = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let order = parse_order(&content.parse()?)?;
+        let sig = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(HookSignature { is_setup: kind == "setup", order, sig })
+    }
+}
+
+impl quote::ToTokens for HookSignature {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let kind = if self.is_setup { quote!(setup) } else { quote!(teardown) };
+        let sign = (self.order < 0).then(|| quote!(-));
+        let order = proc_macro2::Literal::u64_unsuffixed(self.order.unsigned_abs());
+        let sig = &self.sig;
+        tokens.extend(quote!(#kind(#sign #order) #sig;));
+    }
+}
+
+/// A hook of an enclosing fixture, in the order it runs, with the path of its module:
+/// `[path] setup(order) fn name();` or `[path] enter;` for the `#[before_all]` and `#[after_all]` functions.
+enum InheritedHook {
+    Hook(Path, Box<HookSignature>),
+    Enter(Path),
+}
+
+impl Parse for InheritedHook {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let module;
+        bracketed!(module in input);
+        let module = module.parse()?;
+        if input.peek(Ident) && input.peek2(Token![;]) {
+            let name: Ident = input.parse()?;
+            if name != "enter" {
+                return Err(syn::Error::new(name.span(), format!("Unknown inherited hook: {}", name)));
+            }
+            input.parse::<Token![;]>()?;
+            return Ok(InheritedHook::Enter(module));
+        }
+        Ok(InheritedHook::Hook(module, Box::new(input.parse()?)))
+    }
+}
+
+impl quote::ToTokens for InheritedHook {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            InheritedHook::Hook(module, hook) => tokens.extend(quote!([#module] #hook)),
+            InheritedHook::Enter(module) => tokens.extend(quote!([#module] enter;)),
+        }
+    }
+}
+
+fn parse_order(expr: &Expr) -> syn::Result<i64> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: Lit::Int(lit), .. }) => lit.base10_parse(),
+        Expr::Unary(syn::ExprUnary { op: UnOp::Neg(_), expr, .. }) => parse_order(expr).map(|order| -order),
+        _ => Err(syn::Error::new_spanned(expr, "Expected an integer order, e.g. `order = 1`")),
+    }
+}
+
+/// Reads the `order = N` of a `#[setup]` or `#[teardown]` attribute, 0 by default.
+fn hook_order(attr: &syn::Attribute) -> syn::Result<i64> {
+    if let syn::Meta::Path(_) = attr.meta {
+        return Ok(0);
+    }
+    attr.parse_args_with(|input: ParseStream| {
+        let name: Ident = input.parse()?;
+        if name != "order" {
+            return Err(syn::Error::new(name.span(), format!("Unknown option: {}", name)));
+        }
+        input.parse::<Token![=]>()?;
+        parse_order(&input.parse()?)
+    })
+}
+
+/// A setup or teardown function of the fixture, or of a fixture it uses.
+struct Hook {
+    sig: Signature,
+    order: i64,
+    /// The module of a used fixture, relative to the fixture module
+    module: Option<Path>,
+}
+
+impl Hook {
+    fn call(&self, args: TokenStream) -> TokenStream {
+        let name = &self.sig.ident;
+        let module = self.module.as_ref().map(|module| quote!(#module::));
+        let await_token = self.sig.asyncness.map(|_| quote!(.await));
+        quote!(#module #name(#args) #await_token)
+    }
+
+    /// Describes the hook to a nested module, for which the hooks of the module are in the module `self`.
+    fn inherited(&self, is_setup: bool) -> InheritedHook {
+        let module = self.module.clone().unwrap_or_else(|| syn::parse_quote!(self));
+        InheritedHook::Hook(module, Box::new(HookSignature { is_setup, order: self.order, sig: self.sig.clone() }))
+    }
+
+    fn context_type(&self) -> Option<&Type> {
+        match &self.sig.output {
+            ReturnType::Type(_, ty) => Some(ty),
+            ReturnType::Default => None,
+        }
+    }
+}
+
+/// Resolves the path of a used or enclosing fixture, given outside of the fixture module, from inside it.
+fn path_from_module(path: Path) -> Path {
+    if path.leading_colon.is_some() || path.segments.first().is_some_and(|s| s.ident == "crate") {
+        return path;
+    }
+    let mut resolved: Path = syn::parse_quote!(super);
+    for (i, segment) in path.segments.into_iter().enumerate() {
+        if !(i == 0 && segment.ident == "self") {
+            resolved.segments.push(segment);
+        }
+    }
+    resolved
+}
+
 pub fn setup(_attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
     let input_fn = parse2::<ItemFn>(item)?;
     Ok(quote! {
@@ -27,51 +234,188 @@ pub fn teardown(_attr: TokenStream, item: TokenStream) -> syn::Result<TokenStrea
     })
 }
 
-fn process_mod(item_mod: &mut ItemMod) -> syn::Result<()> {
+pub fn before_all(_attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let input_fn = parse2::<ItemFn>(item)?;
+    Ok(quote! {
+        compile_error!("The `#[before_all]` attribute can only be applied to a function within a `#[test_fixture]` annotated module.");
+        #input_fn
+    })
+}
+
+pub fn after_all(_attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let input_fn = parse2::<ItemFn>(item)?;
+    Ok(quote! {
+        compile_error!("The `#[after_all]` attribute can only be applied to a function within a `#[test_fixture]` annotated module.");
+        #input_fn
+    })
+}
+
+fn process_mod(
+    item_mod: &mut ItemMod,
+    used_hooks: Vec<(Path, Vec<HookSignature>)>,
+    inherited: Vec<InheritedHook>,
+) -> syn::Result<()> {
     let mod_span = item_mod.ident.span();
     let (_, items) = item_mod.content.as_mut().ok_or_else(|| {
         syn::Error::new(mod_span, "The `#[test_fixture]` attribute can only be applied to an inline module (with `{ ... }`).")
     })?;
 
-    // 1. Find the setup and teardown functions
-    let mut setup_fn_name = None;
-    let mut teardown_fn_name = None;
-    let mut setup_attr_path = None;
-    let mut teardown_attr_path = None;
+    // 1. Find the setup and teardown functions, after those of the used fixtures
+    let mut setups = Vec::new();
+    let mut teardowns = Vec::new();
+    for (module, hooks) in used_hooks {
+        let module = path_from_module(module);
+        for hook in hooks {
+            let hook_list = if hook.is_setup { &mut setups } else { &mut teardowns };
+            hook_list.push(Hook { sig: hook.sig, order: hook.order, module: Some(module.clone()) });
+        }
+    }
+    let mut own_hooks = Vec::new();
+    let mut attr_paths = Vec::new();
+    let mut before_all_fn = None;
+    let mut after_all_fn = None;
 
     for item in items.iter_mut() {
         if let Item::Fn(item_fn) = item {
-            if let Some(index) = find_attribute_index(item_fn, "setup") {
-                if setup_fn_name.is_some() {
-                    return Err(syn::Error::new_spanned(item_fn, "Only one function can be marked with `#[setup]` in a fixture."));
+            for (attr_name, hook) in [("before_all", &mut before_all_fn), ("after_all", &mut after_all_fn)] {
+                if let Some(index) = find_attribute_index(item_fn, attr_name) {
+                    if hook.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            item_fn,
+                            format!("Only one function can be marked with `#[{}]` in a fixture.", attr_name),
+                        ));
+                    }
+                    if item_fn.sig.asyncness.is_some() || !item_fn.sig.inputs.is_empty() {
+                        return Err(syn::Error::new_spanned(
+                            &item_fn.sig,
+                            format!("The `#[{}]` function can't be async or take parameters.", attr_name),
+                        ));
+                    }
+                    *hook = Some(item_fn.sig.ident.clone());
+                    attr_paths.push(item_fn.attrs[index].path().clone());
+                    item_fn.attrs.remove(index);
                 }
-                setup_fn_name = Some(item_fn.sig.ident.clone());
-                setup_attr_path = Some(item_fn.attrs[index].path().clone());
-                item_fn.attrs.remove(index);
             }
-            if let Some(index) = find_attribute_index(item_fn, "teardown") {
-                if teardown_fn_name.is_some() {
-                    return Err(syn::Error::new_spanned(item_fn, "Only one function can be marked with `#[teardown]` in a fixture."));
+            for (attr_name, is_setup) in [("setup", true), ("teardown", false)] {
+                if let Some(index) = find_attribute_index(item_fn, attr_name) {
+                    let order = hook_order(&item_fn.attrs[index])?;
+                    own_hooks.push(HookSignature { is_setup, order, sig: item_fn.sig.clone() });
+                    let hook = Hook { sig: item_fn.sig.clone(), order, module: None };
+                    if is_setup { setups.push(hook) } else { teardowns.push(hook) }
+                    attr_paths.push(item_fn.attrs[index].path().clone());
+                    item_fn.attrs.remove(index);
                 }
-                teardown_fn_name = Some(item_fn.sig.ident.clone());
-                teardown_attr_path = Some(item_fn.attrs[index].path().clone());
-                item_fn.attrs.remove(index);
             }
         }
     }
 
-    if setup_attr_path.is_none() && teardown_attr_path.is_none() {
-        return Ok(()); // Nothing to do.
+    // Other fixtures can reuse the setup and teardown functions through a macro passing them to `#[test_fixture]`
+    if !own_hooks.is_empty() {
+        items.push(Item::Verbatim(quote! {
+            #[doc(hidden)]
+            macro_rules! __rust_test_fixture {
+                ([$($module:tt)*] { $($options:tt)* } $($item:tt)*) => {
+                    #[rust_test_framework::test_fixture($($options)* __hooks([$($module)*], [#(#own_hooks)*]))]
+                    $($item)*
+                };
+            }
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub(crate) use __rust_test_fixture;
+        }));
     }
 
-    // Add dummy use statements to force the compiler to check if they are imported.
-    if let Some(path) = setup_attr_path {
+    // Setups run by ascending order and in the order of declaration otherwise, teardowns in reverse
+    setups.sort_by_key(|hook| hook.order);
+    teardowns.sort_by_key(|hook| hook.order);
+    teardowns.reverse();
+
+    // The hooks of enclosing fixtures wrap those of the module, from the outermost one
+    let mut enters = Vec::new();
+    let mut inherited_setups = Vec::new();
+    let mut inherited_teardowns = Vec::new();
+    for hook in inherited {
+        match hook {
+            InheritedHook::Enter(module) => enters.push(path_from_module(module)),
+            InheritedHook::Hook(module, hook) => {
+                let hook_list = if hook.is_setup { &mut inherited_setups } else { &mut inherited_teardowns };
+                hook_list.push(Hook { sig: hook.sig, order: hook.order, module: Some(path_from_module(module)) });
+            }
+        }
+    }
+    inherited_setups.append(&mut setups);
+    let setups = inherited_setups;
+    teardowns.append(&mut inherited_teardowns);
+
+    // The tests of the module run `#[before_all]` through a static shared by them
+    if before_all_fn.is_some() || after_all_fn.is_some() {
+        let hook = |name: &Option<syn::Ident>| match name {
+            Some(name) => quote!(::std::option::Option::Some(|| { #name(); })),
+            None => quote!(::std::option::Option::None),
+        };
+        let (before_all, after_all) = (hook(&before_all_fn), hook(&after_all_fn));
         items.insert(0, Item::Verbatim(quote! {
-            #[allow(unused_imports)]
-            use #path as _;
+            static __MODULE_HOOKS: rust_test_framework::__private::runtime::ModuleHooks =
+                rust_test_framework::__private::runtime::ModuleHooks::new();
+
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            fn __rust_test_module_hooks() -> rust_test_framework::__private::runtime::ModuleGuard {
+                __MODULE_HOOKS.enter(#before_all, #after_all, __RUST_TEST_COUNT)
+            }
         }));
+        enters.push(syn::parse_quote!(self));
+    }
+
+    if setups.is_empty() && teardowns.is_empty() && enters.is_empty() && attr_paths.is_empty() {
+        return Ok(()); // Nothing to do.
+    }
+
+    // Nested modules inherit the hooks, and can add their own as fixtures
+    let inherit = enters
+        .iter()
+        .map(|module| InheritedHook::Enter(module.clone()))
+        .chain(setups.iter().map(|hook| hook.inherited(true)))
+        .chain(teardowns.iter().map(|hook| hook.inherited(false)))
+        .collect::<Vec<_>>();
+    // The tests entering the module hooks are counted, so that the last one to finish runs `#[after_all]`
+    let mut test_counts = Vec::new();
+    if !inherit.is_empty() {
+        for item in items.iter_mut() {
+            if let Item::Mod(nested_mod) = item {
+                if nested_mod.content.is_some() {
+                    inherit_hooks(nested_mod, &inherit)?;
+                    let nested = &nested_mod.ident;
+                    test_counts.push(quote!(#nested::__RUST_TEST_COUNT));
+                }
+            }
+        }
     }
-    if let Some(path) = teardown_attr_path {
+
+    // A teardown can take ownership of the context returned by a setup
+    let mut teardown_contexts = Vec::with_capacity(teardowns.len());
+    for teardown in &teardowns {
+        let context = match teardown.sig.inputs.first() {
+            Some(FnArg::Typed(pat_type)) if teardown.sig.inputs.len() == 1 => teardown_context(pat_type, &setups)?,
+            _ => None,
+        };
+        if context.is_none() && !teardown.sig.inputs.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &teardown.sig.inputs,
+                "`#[teardown]` can only take a context returned by a `#[setup]`.",
+            ));
+        }
+        if context.is_some() && teardown_contexts.contains(&context) {
+            return Err(syn::Error::new_spanned(
+                &teardown.sig.inputs,
+                "The context is already taken by another `#[teardown]`.",
+            ));
+        }
+        teardown_contexts.push(context);
+    }
+
+    // Add dummy use statements to force the compiler to check if they are imported.
+    for path in attr_paths {
         items.insert(0, Item::Verbatim(quote! {
             #[allow(unused_imports)]
             use #path as _;
@@ -82,11 +426,125 @@ fn process_mod(item_mod: &mut ItemMod) -> syn::Result<()> {
     for item in items.iter_mut() {
         if let Item::Fn(item_fn) = item {
             if is_test(item_fn) {
-                inject_setup_teardown_calls(item_fn, &setup_fn_name, &teardown_fn_name);
+                // An async setup or teardown can only be awaited by an async test
+                for hook in setups.iter().chain(&teardowns) {
+                    if hook.sig.asyncness.is_some() && item_fn.sig.asyncness.is_none() {
+                        return Err(syn::Error::new_spanned(
+                            &item_fn.sig.ident,
+                            format!("The async `{}` can only be used by async tests.", hook.sig.ident),
+                        ));
+                    }
+                }
+                inject_setup_teardown_calls(item_fn, &setups, &teardowns, &teardown_contexts);
+                if !enters.is_empty() {
+                    test_counts.push(enter_module_hooks(item_fn, &enters));
+                }
             }
         }
     }
 
+    if !enters.is_empty() {
+        items.push(Item::Verbatim(quote! {
+            #[doc(hidden)]
+            pub(super) const __RUST_TEST_COUNT: usize = 0 #(+ #test_counts)*;
+        }));
+    }
+
+    Ok(())
+}
+
+/// Finds the setup whose context a teardown parameter takes: the setup the parameter is named after,
+/// e.g. `open` for `fn close(open: TempDb)`, or else the only setup returning the type of the parameter.
+fn teardown_context(pat_type: &PatType, setups: &[Hook]) -> syn::Result<Option<usize>> {
+    let with_context = || setups.iter().enumerate().filter(|(_, setup)| setup.context_type().is_some());
+    let mut matching: Vec<_> = match &*pat_type.pat {
+        Pat::Ident(pat_ident) => with_context().filter(|(_, setup)| setup.sig.ident == pat_ident.ident).collect(),
+        _ => Vec::new(),
+    };
+    if matching.is_empty() {
+        let ty = &pat_type.ty;
+        let ty = quote!(#ty).to_string();
+        matching = with_context()
+            .filter(|(_, setup)| setup.context_type().is_some_and(|context_type| quote!(#context_type).to_string() == ty))
+            .collect();
+    }
+    match matching[..] {
+        [] => Ok(None),
+        [(index, _)] => Ok(Some(index)),
+        _ => {
+            let names = matching.iter().map(|(_, setup)| format!("`{}`", setup.sig.ident)).collect::<Vec<_>>();
+            Err(syn::Error::new_spanned(
+                pat_type,
+                format!(
+                    "Several `#[setup]` functions return this context, name the parameter after the one to take: {}",
+                    names.join(", ")
+                ),
+            ))
+        }
+    }
+}
+
+/// Makes a test enter the hooks of the modules with `#[before_all]` or `#[after_all]`, and returns the number of
+/// tests it counts for. A plain test enters them first thing; each attribute generating tests is followed by a
+/// `rust_test_enter` marker, so that its tests enter them and it defines a constant with their number.
+fn enter_module_hooks(item_fn: &mut ItemFn, enters: &[Path]) -> TokenStream {
+    let attrs = std::mem::take(&mut item_fn.attrs);
+    let mut counts = Vec::new();
+    for attr in attrs {
+        let generates_tests = is_test_generator(&attr);
+        item_fn.attrs.push(attr);
+        if generates_tests {
+            let count = format_ident!(
+                "__RUST_TEST_COUNT_{}_{}",
+                item_fn.sig.ident.to_string().to_uppercase(),
+                counts.len(),
+                span = Span::call_site()
+            );
+            item_fn.attrs.push(syn::parse_quote!(#[rust_test_framework::rust_test_enter(#count, #(#enters),*)]));
+            counts.push(quote!(#count));
+        }
+    }
+    if !counts.is_empty() {
+        return quote!(#(#counts)+*);
+    }
+
+    for (index, module) in enters.iter().enumerate() {
+        let guard = format_ident!("__rust_test_module_{}", index, span = Span::call_site());
+        let enter: syn::Stmt = syn::parse_quote!(let #guard = #module::__rust_test_module_hooks(););
+        item_fn.block.stmts.insert(index, enter);
+    }
+    let is_ignored = item_fn.attrs.iter().any(|attr| attr.path().segments.last().is_some_and(|s| s.ident == "ignore"));
+    if is_ignored { quote!(0) } else { quote!(1) }
+}
+
+/// Passes the hooks of a fixture to a nested module with the `__inherit` option of its `#[test_fixture]`
+/// attribute, which is added if the module doesn't have one.
+fn inherit_hooks(nested_mod: &mut ItemMod, inherit: &[InheritedHook]) -> syn::Result<()> {
+    let option = quote!(__inherit([#(#inherit)*]));
+    let fixture_attr = nested_mod
+        .attrs
+        .iter_mut()
+        .find(|attr| attr.path().segments.last().is_some_and(|s| s.ident == "test_fixture"));
+    match fixture_attr {
+        Some(attr) => {
+            let path = attr.path().clone();
+            let options = match &attr.meta {
+                syn::Meta::List(list) if !list.tokens.is_empty() => {
+                    let tokens = &list.tokens;
+                    let has_trailing_comma = matches!(
+                        tokens.clone().into_iter().last(),
+                        Some(proc_macro2::TokenTree::Punct(punct)) if punct.as_char() == ','
+                    );
+                    let comma = (!has_trailing_comma).then(|| quote!(,));
+                    quote!(#tokens #comma #option)
+                }
+                syn::Meta::List(_) | syn::Meta::Path(_) => option,
+                syn::Meta::NameValue(_) => return Err(syn::Error::new_spanned(attr, "Expected `#[test_fixture]` or `#[test_fixture(...)]`")),
+            };
+            *attr = syn::parse_quote!(#[#path(#options)]);
+        }
+        None => nested_mod.attrs.push(syn::parse_quote!(#[rust_test_framework::test_fixture(#option)])),
+    }
     Ok(())
 }
 
@@ -101,57 +559,134 @@ fn is_test(item_fn: &ItemFn) -> bool {
     item_fn.attrs.iter().any(|attr| {
         attr.path().is_ident("test") || 
         attr.path().segments.last().map(|s| s.ident == "test").unwrap_or(false) ||
-        attr.path().is_ident("test_params") ||
-        attr.path().segments.last().map(|s| s.ident == "test_params").unwrap_or(false) ||
-        attr.path().is_ident("test_params_source") ||
-        attr.path().segments.last().map(|s| s.ident == "test_params_source").unwrap_or(false)
+        is_test_generator(attr)
     })
 }
 
-fn inject_setup_teardown_calls(item_fn: &mut ItemFn, setup_fn_name: &Option<syn::Ident>, teardown_fn_name: &Option<syn::Ident>) {
-    let setup_call = if let Some(setup_fn_name) = setup_fn_name {
-        quote! {
-            let __setup_result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
-                #setup_fn_name();
-            }));
-            if let ::std::prelude::v1::Err(err) = __setup_result {
-                let msg = if let Some(s) = err.downcast_ref::<&str>() {
-                    s.to_string()
-                } else if let Some(s) = err.downcast_ref::<::std::string::String>() {
-                    s.clone()
-                } else {
-                    "Unknown error".to_string()
-                };
-                panic!("setup failed: {}", msg);
+/// Whether the attribute generates tests from the test function, which becomes their implementation.
+fn is_test_generator(attr: &syn::Attribute) -> bool {
+    attr.path().segments.last().is_some_and(|s| {
+        s.ident == "test_params" || s.ident == "test_params_source" || s.ident == "test_matrix" || s.ident == "test_case"
+    })
+}
+
+/// Removes the parameters of a test that borrow a context returned by a setup, e.g. `ctx: &Ctx` or
+/// `ctx: &mut Ctx`, and returns the statements binding them to it, and which contexts are borrowed mutably.
+fn take_context_params(item_fn: &mut ItemFn, setups: &[Hook]) -> (TokenStream, Vec<bool>) {
+    let context_types: Vec<_> = setups
+        .iter()
+        .map(|setup| setup.context_type().map(|ty| quote!(#ty).to_string()))
+        .collect();
+    let mut bindings = TokenStream::new();
+    let mut is_mutable = vec![false; setups.len()];
+    let inputs = std::mem::take(&mut item_fn.sig.inputs);
+    for input in inputs {
+        if let FnArg::Typed(pat_type) = &input {
+            if let Type::Reference(reference) = pat_type.ty.as_ref() {
+                let elem = &reference.elem;
+                let elem = Some(quote!(#elem).to_string());
+                if let Some(index) = context_types.iter().position(|context_type| *context_type == elem) {
+                    let pat = &pat_type.pat;
+                    let ty = &pat_type.ty;
+                    let mutability = reference.mutability;
+                    let context = context_name(index);
+                    is_mutable[index] |= mutability.is_some();
+                    // The test only runs if all setup functions succeeded
+                    let borrow = if mutability.is_some() { quote!(as_mut) } else { quote!(as_ref) };
+                    bindings.extend(quote!(let #pat: #ty = #context.#borrow().unwrap();));
+                    continue;
+                }
+            }
+        }
+        item_fn.sig.inputs.push(input);
+    }
+    (bindings, is_mutable)
+}
+
+fn context_name(index: usize) -> Ident {
+    format_ident!("__context_{}", index, span = Span::call_site())
+}
+
+/// Wraps the test in calls to the setup and teardown functions. Each of them and the test is a phase whose
+/// panic is caught and reported at the end; the teardown functions run even if a phase before them failed.
+fn inject_setup_teardown_calls(item_fn: &mut ItemFn, setups: &[Hook], teardowns: &[Hook], teardown_contexts: &[Option<usize>]) {
+    if setups.is_empty() && teardowns.is_empty() {
+        return;
+    }
+
+    let is_async = item_fn.sig.asyncness.is_some();
+    // Async tests catch panics while polling their body, synchronous ones while calling it
+    let catch_panic = |body: TokenStream| {
+        if is_async {
+            quote! {
+                rust_test_framework::__private::runtime::catch_panic_async(async { #body }).await
+            }
+        } else {
+            quote! {
+                rust_test_framework::__private::runtime::catch_panic(|| { #body })
             }
         }
-    } else {
-        quote!()
+    };
+    let check = |phase: &str, hook: Option<&Hook>, body: TokenStream| {
+        let function = match hook {
+            Some(hook) => {
+                let name = hook.sig.ident.to_string();
+                quote!(::std::option::Option::Some(#name))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+        let result = catch_panic(body);
+        quote!(__phases.check(#phase, #function, #result))
     };
 
-    let test_body = item_fn.block.stmts.drain(..);
-    let teardown_call = if let Some(teardown_fn_name) = teardown_fn_name {
+    let (context_bindings, is_mutable) = take_context_params(item_fn, setups);
+
+    // A setup function runs if those before it succeeded, its context is `None` otherwise
+    let setup_calls = setups.iter().enumerate().map(|(index, setup)| {
+        let setup_result = check("setup", Some(setup), setup.call(quote!()));
+        let mutability = is_mutable[index].then(|| quote!(mut));
+        let context = context_name(index);
         quote! {
-            let __test_result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
-                #(#test_body)*
-            }));
-            
-            #teardown_fn_name();
+            let #mutability #context = if __phases.failed() {
+                ::std::option::Option::None
+            } else {
+                #setup_result
+            };
+        }
+    });
+
+    let test_body = item_fn.block.stmts.drain(..);
+    let test_result = check("test", None, quote!(#context_bindings #(#test_body)*));
 
-            if let ::std::prelude::v1::Err(err) = __test_result {
-                ::std::panic::resume_unwind(err);
+    // A teardown function taking a context runs if its setup function succeeded
+    let teardown_calls = teardowns.iter().zip(teardown_contexts).map(|(teardown, context)| match context {
+        Some(index) => {
+            let context = context_name(*index);
+            let teardown_result = check("teardown", Some(teardown), teardown.call(quote!(context)));
+            quote! {
+                if let ::std::option::Option::Some(context) = #context {
+                    #teardown_result;
+                }
             }
         }
-    } else {
-        quote! {
-            #(#test_body)*
+        None => {
+            let teardown_result = check("teardown", Some(teardown), teardown.call(quote!()));
+            quote!(#teardown_result;)
         }
-    };
+    });
 
     let new_block: syn::Block = parse2(quote!({
-        #setup_call
-        #teardown_call
+        let mut __phases = rust_test_framework::__private::runtime::Phases::new();
+        #(#setup_calls)*
+        let __test_result = if __phases.failed() {
+            ::std::option::Option::None
+        } else {
+            #test_result
+        };
+        #(#teardown_calls)*
+        // The value the test returns, e.g. a `Result`, is returned after the teardown
+        __phases.finish(__test_result)
     })).expect("Failed to parse setup/teardown call injection");
     
     item_fn.block.stmts = new_block.stmts;
-}
\ No newline at end of file
+}
@@ -0,0 +1,226 @@
+pub(crate) mod covering_array;
+
+use crate::attributes::test_matrix::covering_array::covering_array;
+use crate::attributes::common::{
+    expr_to_values, generate_test_set, parse_item_fn, parse_runtime, set_runtime, unwrap_tuple_structs,
+    validate_param_values, ValueWithSpan, MAX_EXPANDED_CASES,
+};
+use proc_macro2::TokenStream;
+use serde_json::Value;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse2, Expr, Ident, LitInt, Token};
+
+pub fn test_matrix(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let mut input_fn = parse_item_fn(item)?;
+    let fn_name = input_fn.sig.ident.clone();
+
+    let mut args: TestMatrixArgs = parse2(attr)?;
+    let arg_count = input_fn.sig.inputs.len();
+
+    if args.dimensions.len() != arg_count {
+        return Err(syn::Error::new_spanned(
+            &input_fn.sig.inputs,
+            format!(
+                "Test function expects {} arguments, but {} value lists were provided in #[test_matrix]",
+                arg_count,
+                args.dimensions.len()
+            ),
+        ));
+    }
+
+    for (index, values) in args.dimensions.iter_mut().enumerate() {
+        unwrap_tuple_structs(&input_fn, index, values);
+        validate_param_values(&input_fn, index, values)?;
+    }
+
+    let values = match args.strategy {
+        Strategy::Full => {
+            let combinations = args.dimensions.iter().fold(1usize, |n, d| n.saturating_mul(d.len()));
+            if combinations > MAX_EXPANDED_CASES {
+                return Err(syn::Error::new_spanned(
+                    &input_fn.sig.ident,
+                    format!(
+                        "The matrix expands into {} test cases, more than the limit of {}; use `strategy = pairwise`",
+                        combinations, MAX_EXPANDED_CASES
+                    ),
+                ));
+            }
+            cross_product(&args.dimensions)
+        }
+        Strategy::NWise(strength) => {
+            let sizes: Vec<usize> = args.dimensions.iter().map(Vec::len).collect();
+            // Each combination of values of the `strength` largest dimensions needs a row of its own,
+            // so the array isn't built when that alone is over the limit
+            let mut largest = sizes.clone();
+            largest.sort_unstable_by(|a, b| b.cmp(a));
+            let minimum = largest.iter().take(strength).fold(1usize, |n, &size| n.saturating_mul(size));
+            let rows = if minimum > MAX_EXPANDED_CASES { Vec::new() } else { covering_array(&sizes, strength) };
+            let count = rows.len().max(minimum);
+            if count > MAX_EXPANDED_CASES {
+                return Err(syn::Error::new_spanned(
+                    &input_fn.sig.ident,
+                    format!(
+                        "The {}-wise matrix expands into {}{} test cases, more than the limit of {}; use a lower strength",
+                        strength,
+                        if rows.is_empty() { "at least " } else { "" },
+                        count,
+                        MAX_EXPANDED_CASES
+                    ),
+                ));
+            }
+            rows
+                .into_iter()
+                .map(|row| row.iter().enumerate().map(|(d, &v)| &args.dimensions[d][v]).collect())
+                .collect()
+        }
+    }
+    .into_iter()
+    .map(|combination| combine(combination, arg_count))
+    .collect();
+
+    set_runtime(&mut input_fn, args.runtime);
+    generate_test_set(input_fn, values, fn_name, None)
+}
+
+/// Returns every combination of one value from each dimension, varying the last dimension fastest.
+pub(crate) fn cross_product(dimensions: &[Vec<ValueWithSpan>]) -> Vec<Vec<&ValueWithSpan>> {
+    dimensions.iter().fold(vec![Vec::new()], |combinations, dimension| {
+        combinations
+            .into_iter()
+            .flat_map(|combination| {
+                dimension.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.push(value);
+                    combination
+                })
+            })
+            .collect()
+    })
+}
+
+/// Turns a combination of values into a test case, named after the suffixes of its values.
+pub(crate) fn combine(combination: Vec<&ValueWithSpan>, arg_count: usize) -> ValueWithSpan {
+    let suffix = combination.iter().filter_map(|v| v.suffix.clone()).collect::<Vec<_>>().join("_");
+    let span = combination[0].span;
+    let constants = combination.iter().map(|v| v.constant(0).cloned()).collect();
+    let value = if arg_count > 1 {
+        Value::Array(combination.into_iter().map(|v| v.value.clone()).collect())
+    } else {
+        combination[0].value.clone()
+    };
+    ValueWithSpan {
+        value,
+        span,
+        suffix: Some(suffix),
+        expected: None,
+        ignore: None,
+        should_panic: None,
+        raw: None,
+        constants,
+        expected_constant: None,
+        csv_row: None,
+    }
+}
+
+/// How the combinations of values are chosen.
+enum Strategy {
+    /// Every combination (default).
+    Full,
+    /// Enough combinations for every combination of values of any N parameters to be tested.
+    NWise(usize),
+}
+
+/// Arguments of `#[test_matrix]`: a list of values for each parameter, e.g. `[1, 2], ["a", "b"]`,
+/// optionally followed by `strategy = full | pairwise | nwise(N)` and `runtime = "..."`.
+struct TestMatrixArgs {
+    dimensions: Vec<Vec<ValueWithSpan>>,
+    strategy: Strategy,
+    /// Test attribute of the async runtime running async tests, given with `runtime = "tokio::test"`.
+    runtime: Option<syn::Meta>,
+}
+
+fn parse_strategy(input: ParseStream) -> syn::Result<Strategy> {
+    let name: Ident = input.parse()?;
+    match name.to_string().as_str() {
+        "full" => Ok(Strategy::Full),
+        "pairwise" => Ok(Strategy::NWise(2)),
+        "nwise" => {
+            let content;
+            syn::parenthesized!(content in input);
+            let strength: LitInt = content.parse()?;
+            match strength.base10_parse::<usize>()? {
+                0 => Err(syn::Error::new_spanned(strength, "Expected a strength of at least 1")),
+                n => Ok(Strategy::NWise(n)),
+            }
+        }
+        _ => Err(syn::Error::new_spanned(
+            name,
+            "Expected `full`, `pairwise` or `nwise(N)`",
+        )),
+    }
+}
+
+impl Parse for TestMatrixArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut dimensions = Vec::new();
+        let mut strategy = Strategy::Full;
+        let mut runtime = None;
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                let key: Ident = input.parse()?;
+                input.parse::<Token![=]>()?;
+                match key.to_string().as_str() {
+                    "strategy" => strategy = parse_strategy(input)?,
+                    "runtime" => runtime = Some(parse_runtime(input)?),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &key,
+                            format!("Unknown test_matrix option: {}", key),
+                        ));
+                    }
+                }
+                if input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+
+            let expr: Expr = input.parse()?;
+            let dimension = match &expr {
+                Expr::Array(array) if array.elems.is_empty() => {
+                    return Err(syn::Error::new_spanned(array, "Expected at least one value"));
+                }
+                Expr::Array(array) => {
+                    let mut values = Vec::new();
+                    for elem in &array.elems {
+                        values.extend(expr_to_values(elem)?);
+                    }
+                    values
+                }
+                Expr::Range(_) => expr_to_values(&expr)?,
+                Expr::Call(call) if matches!(&*call.func, Expr::Path(p) if p.path.is_ident("range")) => {
+                    expr_to_values(&expr)?
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        expr,
+                        "Expected a list of values for each parameter, e.g. `[1, 2, 3]` or `1..=3`",
+                    ));
+                }
+            };
+            dimensions.push(dimension);
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        if dimensions.is_empty() {
+            return Err(input.error("Expected at least one list of values"));
+        }
+
+        Ok(TestMatrixArgs { dimensions, strategy, runtime })
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeSet;
+
+/// A `strength`-way interaction: parameter indices in ascending order, and a value index for each.
+type Interaction = (Vec<usize>, Vec<usize>);
+
+/// Builds a covering array with the IPOG (In-Parameter-Order-General) strategy.
+///
+/// `sizes` holds the number of values of each parameter. Every combination of values of any `strength`
+/// parameters appears in at least one returned row, where a row holds a value index for each parameter.
+/// The result only depends on the input, so the generated test cases are stable across builds.
+pub(crate) fn covering_array(sizes: &[usize], strength: usize) -> Vec<Vec<usize>> {
+    let strength = strength.clamp(1, sizes.len().max(1));
+
+    // Start with all combinations of the first `strength` parameters
+    let mut rows: Vec<Vec<Option<usize>>> = vec![Vec::new()];
+    for &size in &sizes[..strength.min(sizes.len())] {
+        rows = rows
+            .into_iter()
+            .flat_map(|row| {
+                (0..size).map(move |value| {
+                    let mut row = row.clone();
+                    row.push(Some(value));
+                    row
+                })
+            })
+            .collect();
+    }
+
+    // Extend the array by one parameter at a time
+    for param in strength..sizes.len() {
+        let mut uncovered = interactions_with(sizes, param, strength);
+
+        // Horizontal growth: pick the value covering the most uncovered interactions for each existing row
+        for row in &mut rows {
+            let best = (0..sizes[param])
+                .max_by_key(|&value| {
+                    row.push(Some(value));
+                    let count = uncovered.iter().filter(|i| covers(row, i)).count();
+                    row.pop();
+                    // Prefer the lowest value among equally good ones
+                    (count, std::cmp::Reverse(value))
+                })
+                .unwrap();
+            row.push(Some(best));
+            uncovered.retain(|i| !covers(row, i));
+        }
+
+        // Vertical growth: fill unset values of existing rows, or add rows, for the interactions left
+        for interaction in &uncovered {
+            if rows.iter().any(|row| covers(row, interaction)) {
+                continue;
+            }
+            let (params, values) = interaction;
+            let compatible = rows.iter_mut().find(|row| {
+                params
+                    .iter()
+                    .zip(values)
+                    .all(|(&p, &v)| !matches!(row[p], Some(current) if current != v))
+            });
+            let row = match compatible {
+                Some(row) => row,
+                None => {
+                    rows.push(vec![None; param + 1]);
+                    rows.last_mut().unwrap()
+                }
+            };
+            for (&p, &v) in params.iter().zip(values) {
+                row[p] = Some(v);
+            }
+        }
+    }
+
+    // Unset values can take any value; different rows may become equal, keep the first of them
+    let mut seen = BTreeSet::new();
+    rows.into_iter()
+        .map(|row| row.into_iter().map(|v| v.unwrap_or(0)).collect::<Vec<_>>())
+        .filter(|row| seen.insert(row.clone()))
+        .collect()
+}
+
+/// Returns all `strength`-way interactions between `param` and the parameters before it.
+fn interactions_with(sizes: &[usize], param: usize, strength: usize) -> BTreeSet<Interaction> {
+    let mut interactions = BTreeSet::new();
+    for mut params in subsets(param, strength - 1) {
+        params.push(param);
+        let mut values = vec![Vec::new()];
+        for &p in &params {
+            values = values
+                .into_iter()
+                .flat_map(|prefix: Vec<usize>| {
+                    (0..sizes[p]).map(move |v| {
+                        let mut values = prefix.clone();
+                        values.push(v);
+                        values
+                    })
+                })
+                .collect();
+        }
+        for values in values {
+            interactions.insert((params.clone(), values));
+        }
+    }
+    interactions
+}
+
+/// Returns all subsets of `k` elements of `0..n`, each in ascending order.
+fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    (k - 1..n)
+        .flat_map(|last| {
+            subsets(last, k - 1).into_iter().map(move |mut subset| {
+                subset.push(last);
+                subset
+            })
+        })
+        .collect()
+}
+
+fn covers(row: &[Option<usize>], (params, values): &Interaction) -> bool {
+    params
+        .iter()
+        .zip(values)
+        .all(|(&p, &v)| row.get(p).copied().flatten() == Some(v))
+}
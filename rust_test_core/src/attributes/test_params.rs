@@ -1,19 +1,39 @@
 use crate::attributes::common::{
-    expr_to_value_with_span, generate_test_set, parse_item_fn, ValueWithSpan,
+    constant_to_value_with_span, expr_to_value_with_span, expr_to_values, generate_test_set, parse_item_fn,
+    parse_runtime, reject_constant, return_type, set_runtime, unwrap_tuple_struct, unwrap_tuple_structs, validate_param_values,
+    validate_test_name, validate_type_match, ValueWithSpan,
+    MAX_EXPANDED_CASES,
 };
+use crate::attributes::test_matrix::{combine, cross_product};
 use proc_macro2::TokenStream;
 use serde_json::Value;
 use syn::parse::{Parse, ParseStream};
-use syn::{parse2, Expr, Token};
+use syn::{parse2, Expr, Ident, Lit, LitStr, Token};
 
 pub fn test_params(_attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
-    let input_fn = parse_item_fn(item)?;
+    let mut input_fn = parse_item_fn(item)?;
     let fn_name = input_fn.sig.ident.clone();
 
-    let args: TestCaseArgs = parse2(_attr)?;
+    let mut args: TestCaseArgs = parse2(_attr)?;
     let arg_count = input_fn.sig.inputs.len();
 
-    let values = if arg_count > 1 {
+    if let Some(name) = &args.name {
+        validate_test_name(&fn_name, name)?;
+    }
+
+    if arg_count == 1 {
+        for values in &mut args.values {
+            unwrap_tuple_structs(&input_fn, 0, values);
+            validate_param_values(&input_fn, 0, values)?;
+        }
+    } else if args.values.len() == arg_count {
+        for (index, values) in args.values.iter_mut().enumerate() {
+            unwrap_tuple_structs(&input_fn, index, values);
+            validate_param_values(&input_fn, index, values)?;
+        }
+    }
+
+    let mut values: Vec<ValueWithSpan> = if arg_count > 1 {
         if args.values.len() != arg_count {
             return Err(syn::Error::new_spanned(
                 &input_fn.sig.inputs,
@@ -24,15 +44,23 @@ pub fn test_params(_attr: TokenStream, item: TokenStream) -> syn::Result<TokenSt
                 ),
             ));
         }
-        let span = args.values[0].span; // Use the first arg span for the array
-        let suffix = args.values.iter().filter_map(|v| v.suffix.clone()).collect::<Vec<_>>().join("_");
-        vec![ValueWithSpan {
-            value: Value::Array(args.values.into_iter().map(|v| v.value).collect()),
-            span,
-            suffix: Some(suffix),
-        }]
+        // Ranges expand into a test case per combination of their elements
+        let combinations = args.values.iter().fold(1usize, |n, v| n.saturating_mul(v.len()));
+        if combinations > MAX_EXPANDED_CASES {
+            return Err(syn::Error::new(
+                args.values[0][0].span,
+                format!(
+                    "The ranges expand into {} test cases, more than the limit of {}; use a larger step",
+                    combinations, MAX_EXPANDED_CASES
+                ),
+            ));
+        }
+        cross_product(&args.values)
+            .into_iter()
+            .map(|combination| combine(combination, arg_count))
+            .collect()
     } else if arg_count == 1 {
-        args.values
+        args.values.into_iter().flatten().collect()
     } else {
         return Err(syn::Error::new_spanned(
             &input_fn.sig.inputs,
@@ -40,23 +68,103 @@ pub fn test_params(_attr: TokenStream, item: TokenStream) -> syn::Result<TokenSt
         ));
     };
 
+    if let Some(name) = args.name {
+        if values.len() > 1 {
+            return Err(syn::Error::new_spanned(
+                name,
+                "`name` can only be used with a single test case per #[test_params]",
+            ));
+        }
+        values[0].suffix = Some(name.value());
+    }
+
+    if let Some(mut expect) = args.expect {
+        let return_type = return_type(&input_fn).ok_or_else(|| {
+            syn::Error::new(expect.span, "`expect` requires the test function to return a value")
+        })?;
+        if values.len() > 1 {
+            return Err(syn::Error::new(
+                expect.span,
+                "`expect` can only be used with a single test case per #[test_params]",
+            ));
+        }
+        if let Some(constant) = expect.constant(0) {
+            values[0].expected_constant = Some(constant.clone());
+        } else {
+            unwrap_tuple_struct(return_type, &mut expect.value);
+            validate_type_match(return_type, &expect.value, expect.span)?;
+            values[0].expected = Some(expect.value);
+        }
+    }
+
+    for value in &mut values {
+        value.ignore = args.ignore.clone();
+        value.should_panic = args.should_panic.clone();
+    }
+
+    set_runtime(&mut input_fn, args.runtime);
     generate_test_set(input_fn, values, fn_name, None)
 }
 
 struct TestCaseArgs {
-    values: Vec<ValueWithSpan>,
+    /// Values of each argument; a range has a value per element.
+    values: Vec<Vec<ValueWithSpan>>,
+    /// Name of the test case given with `name = "..."`, replacing the suffix derived from the value.
+    name: Option<LitStr>,
+    /// Value the test function must return, given with `expect = ...`.
+    expect: Option<ValueWithSpan>,
+    /// Reason given with `ignore = "..."`, or empty for `ignore = true`.
+    ignore: Option<String>,
+    /// Expected panic message given with `should_panic = "..."`, or empty for `should_panic = true`.
+    should_panic: Option<String>,
+    /// Test attribute of the async runtime running async tests, given with `runtime = "tokio::test"`.
+    runtime: Option<syn::Meta>,
+}
+
+/// Parses the value of a marker option: a string, or a boolean to set it without a message.
+pub(crate) fn parse_marker(input: ParseStream) -> syn::Result<Option<String>> {
+    match input.parse::<Lit>()? {
+        Lit::Str(s) => Ok(Some(s.value())),
+        Lit::Bool(b) => Ok(b.value.then(String::new)),
+        other => Err(syn::Error::new_spanned(other, "Expected a string or a boolean")),
+    }
 }
 
 impl Parse for TestCaseArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut values = Vec::new();
+        let mut name = None;
+        let mut expect = None;
+        let mut ignore = None;
+        let mut should_panic = None;
+        let mut runtime = None;
         while !input.is_empty() {
-            if input.peek(syn::token::Paren) {
+            if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+                let key: Ident = input.parse()?;
+                input.parse::<Token![=]>()?;
+                match key.to_string().as_str() {
+                    "name" => name = Some(input.parse::<LitStr>()?),
+                    "expect" => {
+                        let expr: Expr = input.parse()?;
+                        expect = Some(expr_to_value_with_span(&expr)?);
+                    }
+                    "ignore" => ignore = parse_marker(input)?,
+                    "should_panic" => should_panic = parse_marker(input)?,
+                    "runtime" => runtime = Some(parse_runtime(input)?),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &key,
+                            format!("Unknown test_params option: {}", key),
+                        ));
+                    }
+                }
+            } else if input.peek(syn::token::Paren) {
                 let content;
                 let paren_token = syn::parenthesized!(content in input);
                 let mut tuple_values = Vec::new();
                 while !content.is_empty() {
                     let expr: Expr = content.parse()?;
+                    reject_constant(&expr)?;
                     tuple_values.push(expr_to_value_with_span(&expr)?);
                     if content.peek(Token![,]) {
                         content.parse::<Token![,]>()?;
@@ -65,14 +173,27 @@ impl Parse for TestCaseArgs {
                     }
                 }
                 let suffix = tuple_values.iter().filter_map(|v| v.suffix.clone()).collect::<Vec<_>>().join("_");
-                values.push(ValueWithSpan {
+                values.push(vec![ValueWithSpan {
                     value: Value::Array(tuple_values.into_iter().map(|v| v.value).collect()),
                     span: paren_token.span.join(),
                     suffix: Some(suffix),
-                });
+                    expected: None,
+                    ignore: None,
+                    should_panic: None,
+                    raw: None,
+                    constants: Vec::new(),
+                    expected_constant: None,
+                    csv_row: None,
+                }]);
+            } else if input.peek(Token![const]) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
+                // `const: limits` passes a constant or static whose name doesn't look like one
+                input.parse::<Token![const]>()?;
+                input.parse::<Token![:]>()?;
+                let expr: Expr = input.parse()?;
+                values.push(vec![constant_to_value_with_span(&expr)?]);
             } else {
                 let expr: Expr = input.parse()?;
-                values.push(expr_to_value_with_span(&expr)?);
+                values.push(expr_to_values(&expr)?);
             }
 
             if input.peek(Token![,]) {
@@ -86,6 +207,13 @@ impl Parse for TestCaseArgs {
             return Err(input.error("Expected at least one test case value"));
         }
 
-        Ok(TestCaseArgs { values })
+        Ok(TestCaseArgs {
+            values,
+            name,
+            expect,
+            ignore,
+            should_panic,
+            runtime,
+        })
     }
 }
@@ -1,11 +1,20 @@
 mod source_type;
+mod csv_data;
 
-pub use crate::attributes::test_params_source::source_type::SourceType;
-use crate::attributes::common::{generate_test_set, parse_item_fn, ValueWithSpan, is_path_type, check_json_compatibility};
-use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+pub use crate::attributes::test_params_source::source_type::{CsvOptions, GeneratedOptions, SourceType};
+use crate::attributes::test_params_source::csv_data::{csv_to_values, has_positional_cells};
+use crate::attributes::common::{
+    assert_success, case_type_token, check_json_compatibility, deserialize_and_call, generate_test_set, impl_fn_names,
+    is_path_type, parse_item_fn, parse_runtime, return_type, set_runtime, split_expected, split_markers,
+    validate_type_match, value_to_suffix, ModuleEnter, Runner, ValueWithSpan,
+};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use serde::Deserialize;
 use serde_json::Value;
-use syn::{parse2, LitStr, Type};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse2, Ident, ItemFn, LitStr, Token, Type};
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 static CLIENT: LazyLock<reqwest::blocking::Client> = LazyLock::new(|| {
@@ -15,49 +24,403 @@ static CLIENT: LazyLock<reqwest::blocking::Client> = LazyLock::new(|| {
         .expect("Failed to create reqwest client")
 });
 
+/// When the data of a source is read.
+#[derive(Clone, Copy, PartialEq)]
+enum LoadMode {
+    /// The data is read by the macro and a test is generated per case (default).
+    CompileTime,
+    /// The data is read when the test runs, and a single test runs all cases.
+    Runtime,
+}
+
+/// Arguments of the `test_params_source` attribute: the source followed by options,
+/// e.g. `JsonFile("data.json"), load = runtime, name_field = "id"`.
+struct TestParamsSourceArgs {
+    source: SourceType,
+    /// The tokens the source was parsed from, passed on as they are to sources read at run time.
+    source_tokens: TokenStream,
+    load: LoadMode,
+    /// Field of each test case that names its test, replacing the suffix derived from the value.
+    name_field: Option<LitStr>,
+    /// Test attribute of the async runtime running async tests, given with `runtime = "tokio::test"`.
+    runtime: Option<syn::Meta>,
+}
+
+impl Parse for TestParamsSourceArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let start = input.cursor();
+        let source: SourceType = input.parse().map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                format!("Expected [`rust_test::SourceType`] variant: {}", e),
+            )
+        })?;
+        // Commas may appear in a turbofish type of the source, so its tokens are the ones its parser consumed
+        let mut source_tokens = TokenStream::new();
+        let mut cursor = start;
+        while cursor != input.cursor() {
+            let Some((token, next)) = cursor.token_tree() else { break };
+            source_tokens.extend([token]);
+            cursor = next;
+        }
+
+        let mut load = LoadMode::CompileTime;
+        let mut name_field = None;
+        let mut runtime = None;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "load" => {
+                    let mode: Ident = input.parse()?;
+                    load = match mode.to_string().as_str() {
+                        "compile_time" => LoadMode::CompileTime,
+                        "runtime" => LoadMode::Runtime,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                mode,
+                                "Expected `compile_time` or `runtime`",
+                            ))
+                        }
+                    };
+                }
+                "name_field" => name_field = Some(input.parse::<LitStr>()?),
+                "runtime" => runtime = Some(parse_runtime(input)?),
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!("Unknown test_params_source option: {}", other),
+                    ))
+                }
+            }
+        }
+
+        Ok(TestParamsSourceArgs { source, source_tokens, load, name_field, runtime })
+    }
+}
+
 pub fn test_params_source(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
-    let source: SourceType = parse2(attr).map_err(|e| {
-        syn::Error::new(
-            e.span(),
-            format!("Expected [`rust_test::SourceType`] variant: {}", e),
-        )
-    })?;
-    let source_span = source.span();
-    let input_fn = parse_item_fn(item)?;
+    let args: TestParamsSourceArgs = parse2(attr)?;
+    let mut input_fn = parse_item_fn(item)?;
     let fn_name = input_fn.sig.ident.clone();
 
-    let mut type_name = None;
-    if input_fn.sig.inputs.len() == 1 {
-        if let Some(syn::FnArg::Typed(pat_type)) = input_fn.sig.inputs.first() {
-            type_name = Some((*pat_type.ty).clone());
+    // A single test runs all these cases, blocking on each call of an async test function
+    let single_test = args.load == LoadMode::Runtime || matches!(args.source, SourceType::Generated(..));
+    if let Some(runtime) = args.runtime.as_ref().filter(|_| single_test) {
+        return Err(syn::Error::new_spanned(
+            runtime,
+            "`runtime` can only be used with test cases loaded at compile time",
+        ));
+    }
+
+    if let SourceType::Generated(options, _) = &args.source {
+        if let Some(name_field) = &args.name_field {
+            return Err(syn::Error::new_spanned(name_field, "`name_field` can't be used with generated test cases"));
         }
+        return generate_property_test(options, args.source_tokens, input_fn);
     }
-    let type_name_opt = type_name;
 
-    // 1. Extract parameter type from function if not provided in attribute
-    let (json_content, mut type_name, file_info): (String, Option<Type>, Option<(LitStr, String)>) = match source {
-        SourceType::JsonFile(ref path, ref ty, _) => {
-            let file_path_value = path.value();
-            // Resolve the full path
-            let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").ok_or_else(|| {
-                syn::Error::new_spanned(path, "CARGO_MANIFEST_DIR not set")
-            })?;
-            let full_path = std::path::Path::new(&manifest_dir).join(&file_path_value);
-            let file_path_literal = full_path.to_str().ok_or_else(|| {
-                syn::Error::new_spanned(path, "Path contains invalid UTF-8")
-            })?;
+    if args.load == LoadMode::Runtime {
+        return generate_runtime_test(&args.source, args.source_tokens, args.name_field, input_fn);
+    }
 
-            // Read the file
-            let content = std::fs::read_to_string(&full_path).map_err(|e| {
-                syn::Error::new_spanned(
-                    path,
-                    format!("Could not read file {}: {}", full_path.display(), e),
-                )
-            })?;
-            (content, ty.clone(), Some((path.clone(), file_path_literal.to_string())))
+    set_runtime(&mut input_fn, args.runtime);
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR");
+    let mut loaded = load_source(&args.source, &input_fn, manifest_dir.as_deref().map(Path::new))?;
+    if let Some(name_field) = &args.name_field {
+        name_cases(&mut loaded.cases, &name_field.value())
+            .map_err(|e| syn::Error::new_spanned(name_field, e))?;
+    }
+    let tests_stream = generate_test_set(input_fn, loaded.cases, fn_name.clone(), loaded.type_name)?;
+
+    if loaded.files.is_empty() {
+        return Ok(tests_stream);
+    }
+
+    // Include the data files so that changing them triggers a rebuild
+    let file_path_consts = loaded.files.iter().enumerate().map(|(i, file_path_literal)| {
+        let const_name = if let SourceType::JsonGlob(..) = args.source {
+            format_ident!("{}_DATA_{}", fn_name.to_string().to_uppercase(), i)
+        } else {
+            format_ident!("{}_DATA", fn_name.to_string().to_uppercase())
+        };
+        quote! { const #const_name: &str = include_str!(#file_path_literal); }
+    });
+    // Output the consts + generated tests
+    Ok(quote! {
+        /// --- Test data source
+        #(#file_path_consts)*
+        #tests_stream
+    })
+}
+
+/// Generates a single test that loads the source when it runs and executes every case.
+fn generate_runtime_test(
+    source: &SourceType,
+    source_tokens: TokenStream,
+    name_field: Option<LitStr>,
+    mut input_fn: ItemFn,
+) -> syn::Result<TokenStream> {
+    if input_fn.sig.inputs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input_fn.sig,
+            "test generation from source requires at least one parameter",
+        ));
+    }
+
+    let (real_fn_name, impl_fn_name) = impl_fn_names(&input_fn.sig.ident);
+    input_fn.sig.ident = impl_fn_name.clone();
+    let enter = ModuleEnter::take(&mut input_fn)?;
+    let (enter, test_count) = match enter {
+        Some(enter) => (enter.statements(), enter.count(1)),
+        None => (TokenStream::new(), TokenStream::new()),
+    };
+
+    let type_name = source.type_name().cloned().or_else(|| single_param_type(&input_fn));
+    let case_type = case_type_token(&input_fn, type_name)?;
+    let arg_count = input_fn.sig.inputs.len();
+    // Whether a test case has an expected result is only known once the data is loaded
+    let check = return_type(&input_fn).map(|_| {
+        let assert_success = assert_success(quote!(json));
+        quote! {
+            if let Some(expected) = expected {
+                use rust_test_framework::__private::runtime::{CannotCompareExpected as _, CompareExpected as _};
+                (&&rust_test_framework::__private::runtime::Returned::new(actual)).assert_expected_json(expected);
+            } else {
+                #assert_success
+            }
+        }
+    });
+    let expected_param = if check.is_some() { quote!(expected) } else { quote!(_expected) };
+    let runner = Runner::new(&input_fn, None)?;
+    // Struct rows of a CSV file are deserialized field by field, see `csv_to_values`
+    let csv_row = (matches!(source, SourceType::CsvFile(..)) && !has_positional_cells(&input_fn))
+        .then(|| quote!(case.csv_row));
+    let call_expr = runner.block_on(deserialize_and_call(
+        &impl_fn_name,
+        quote!(json),
+        csv_row,
+        &case_type,
+        arg_count,
+        check,
+        &runner,
+    ));
+
+    let suffix = match source {
+        SourceType::JsonString(..) => "json_string".to_string(),
+        SourceType::YamlString(..) => "yaml_string".to_string(),
+        SourceType::JsonFile(path, ..)
+        | SourceType::JsonResponse(path, ..)
+        | SourceType::JsonLines(path, ..)
+        | SourceType::JsonGlob(path, ..)
+        | SourceType::YamlFile(path, ..)
+        | SourceType::TomlFile(path, ..)
+        | SourceType::CsvFile(path, ..)
+        | SourceType::PathMask(path, ..) => path_to_suffix(&path.value()),
+        SourceType::Generated(..) => "generated".to_string(),
+    };
+    let test_fn_name = format_ident!("{}__rt_{}", real_fn_name, suffix);
+    let docstring = format!("Generated test {} loading {} at run time", real_fn_name, source_tokens);
+
+    // The signature is enough to validate and convert the data the same way the macro does
+    let source_str = source_tokens.to_string();
+    let sig = &input_fn.sig;
+    let signature_str = quote!(#sig {}).to_string();
+    let name_field = match name_field {
+        Some(field) => quote!(Some(#field)),
+        None => quote!(None),
+    };
+
+    Ok(quote! {
+        /// Original test function
+        #input_fn
+        #test_count
+        #[doc = #docstring]
+        #[test]
+        #[allow(non_snake_case)]
+        fn #test_fn_name() {
+            #enter
+            let cases = rust_test_framework::__private::runtime::load_cases(
+                #source_str,
+                #signature_str,
+                env!("CARGO_MANIFEST_DIR"),
+                #name_field,
+            );
+            rust_test_framework::__private::runtime::run_cases(cases, |case| {
+                let json = case.input.as_str();
+                let #expected_param = case.expected.as_deref();
+                #call_expr
+            });
+        }
+    })
+}
+
+/// Generates a single test that runs the function with random inputs and shrinks the first failing one.
+fn generate_property_test(
+    options: &GeneratedOptions,
+    source_tokens: TokenStream,
+    mut input_fn: ItemFn,
+) -> syn::Result<TokenStream> {
+    if input_fn.sig.inputs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input_fn.sig,
+            "test generation from source requires at least one parameter",
+        ));
+    }
+
+    let (real_fn_name, impl_fn_name) = impl_fn_names(&input_fn.sig.ident);
+    input_fn.sig.ident = impl_fn_name.clone();
+    let enter = ModuleEnter::take(&mut input_fn)?;
+    let (enter, test_count) = match enter {
+        Some(enter) => (enter.statements(), enter.count(1)),
+        None => (TokenStream::new(), TokenStream::new()),
+    };
+
+    // Inputs are generated as owned values and borrowed for reference parameters
+    let mut value_types = Vec::new();
+    let mut arg_names = Vec::new();
+    let mut call_args = Vec::new();
+    for (i, arg) in input_fn.sig.inputs.iter().enumerate() {
+        let syn::FnArg::Typed(pat_type) = arg else {
+            return Err(syn::Error::new_spanned(arg, "test functions can't take `self`"));
+        };
+        let arg_name = format_ident!("arg_{}", i);
+        match &*pat_type.ty {
+            Type::Reference(reference) => {
+                value_types.push(owned_type(&reference.elem));
+                call_args.push(quote!(&#arg_name));
+            }
+            ty => {
+                value_types.push(quote!(#ty));
+                call_args.push(quote!(#arg_name));
+            }
+        }
+        arg_names.push(arg_name);
+    }
+    let (value_type, pattern) = if value_types.len() == 1 {
+        (value_types[0].clone(), arg_names[0].to_token_stream())
+    } else {
+        (quote!((#(#value_types,)*)), quote!((#(#arg_names,)*)))
+    };
+
+    let strategy = match &options.strategy {
+        Some(strategy) => quote!(#strategy),
+        None => quote!(rust_test_framework::generated::any::<#value_type>()),
+    };
+    let cases = match &options.cases {
+        Some(cases) => cases.base10_parse::<u32>()?,
+        None => crate::generated::DEFAULT_CASES,
+    };
+    let seed = match &options.seed {
+        Some(seed) => {
+            let seed = seed.base10_parse::<u64>()?;
+            quote!(Some(#seed))
+        }
+        None => quote!(None),
+    };
+
+    let runner = Runner::new(&input_fn, None)?;
+    let await_token = runner.await_token();
+    let call = quote!(#impl_fn_name(#(#call_args),*) #await_token);
+    // The input is described before the arguments are moved into the call
+    let (closure_param, call_expr) = match return_type(&input_fn) {
+        Some(_) => {
+            let check = assert_success(quote!(&input));
+            (
+                quote!(value: #value_type),
+                quote! {
+                    let input = format!("{:?}", value);
+                    let #pattern = value;
+                    let actual = #call;
+                    #check
+                },
+            )
+        }
+        None => (quote!(#pattern: #value_type), quote!(#call;)),
+    };
+    let call_expr = runner.block_on(call_expr);
+
+    let test_fn_name = format_ident!("{}__generated", real_fn_name);
+    let docstring = format!("Generated test {} running {} cases of {}", real_fn_name, cases, source_tokens);
+
+    Ok(quote! {
+        /// Original test function
+        #input_fn
+        #test_count
+        #[doc = #docstring]
+        #[test]
+        #[allow(non_snake_case)]
+        fn #test_fn_name() {
+            #enter
+            rust_test_framework::generated::run(#strategy, #cases, #seed, |#closure_param| {
+                #call_expr
+            });
+        }
+    })
+}
+
+/// The owned counterpart of a borrowed parameter type: `str` is generated as a `String`
+/// and a slice `[T]` as a `Vec<T>`.
+fn owned_type(ty: &Type) -> TokenStream {
+    match ty {
+        Type::Path(tp) if tp.path.is_ident("str") => quote!(String),
+        Type::Slice(slice) => {
+            let elem = &slice.elem;
+            quote!(Vec<#elem>)
+        }
+        ty => quote!(#ty),
+    }
+}
+
+/// Test cases read from a data source.
+pub(crate) struct LoadedSource {
+    pub cases: Vec<ValueWithSpan>,
+    /// Type to deserialize each case into, if it can't be inferred from the parameters.
+    pub type_name: Option<Type>,
+    /// Absolute paths of the files the cases were read from.
+    pub files: Vec<String>,
+}
+
+/// Reads a data source and converts it into test cases for `input_fn`.
+///
+/// Files are resolved relative to `manifest_dir`.
+pub(crate) fn load_source(
+    source: &SourceType,
+    input_fn: &ItemFn,
+    manifest_dir: Option<&Path>,
+) -> syn::Result<LoadedSource> {
+    let source_span = source.span();
+    let type_name_opt = single_param_type(input_fn);
+
+    // 1. Extract parameter type from function if not provided in attribute
+    let (content, mut type_name, file_info, format): (String, Option<Type>, Option<(LitStr, String)>, DataFormat) = match source {
+        SourceType::JsonFile(path, ty, _, _) => {
+            let (content, file_path_literal) = read_source_file(path, manifest_dir)?;
+            (content, ty.clone(), Some((path.clone(), file_path_literal)), DataFormat::Json)
+        }
+        SourceType::JsonString(json_str, ty, _, _) => (json_str.value(), ty.clone(), None, DataFormat::Json),
+        SourceType::JsonLines(path, ty, _) => {
+            let (content, file_path_literal) = read_source_file(path, manifest_dir)?;
+            (content, ty.clone(), Some((path.clone(), file_path_literal)), DataFormat::JsonLines)
+        }
+        SourceType::YamlFile(path, ty, _) => {
+            let (content, file_path_literal) = read_source_file(path, manifest_dir)?;
+            (content, ty.clone(), Some((path.clone(), file_path_literal)), DataFormat::Yaml)
         }
-        SourceType::JsonString(ref json_str, ref ty, _) => (json_str.value(), ty.clone(), None),
-        SourceType::JsonResponse(ref url, ref ty, _) => {
+        SourceType::YamlString(yaml_str, ty, _) => (yaml_str.value(), ty.clone(), None, DataFormat::Yaml),
+        SourceType::TomlFile(path, ty, _) => {
+            let (content, file_path_literal) = read_source_file(path, manifest_dir)?;
+            (content, ty.clone(), Some((path.clone(), file_path_literal)), DataFormat::Toml)
+        }
+        SourceType::CsvFile(path, ty, _, _) => {
+            let (content, file_path_literal) = read_source_file(path, manifest_dir)?;
+            (content, ty.clone(), Some((path.clone(), file_path_literal)), DataFormat::Csv)
+        }
+        SourceType::JsonResponse(url, ty, _, _) => {
             let url_value = url.value();
             let response = CLIENT.get(&url_value)
                 .send()
@@ -82,61 +445,72 @@ pub fn test_params_source(attr: TokenStream, item: TokenStream) -> syn::Result<T
                         format!("Could not read response from {}: {}", url_value, e),
                     )
                 })?;
-            (content, ty.clone(), None)
+            (content, ty.clone(), None, DataFormat::Json)
         }
-        SourceType::PathMask(ref mask, _) => {
-            let mask_value = mask.value();
-            let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").ok_or_else(|| {
-                syn::Error::new_spanned(mask, "CARGO_MANIFEST_DIR not set")
-            })?;
-            let full_mask = std::path::Path::new(&manifest_dir).join(&mask_value);
-            let full_mask_str = full_mask.to_str().ok_or_else(|| {
-                syn::Error::new_spanned(mask, "Path mask contains invalid UTF-8")
-            })?;
-
-            let matches: Vec<_> = glob::glob(full_mask_str)
-                .map_err(|e| syn::Error::new_spanned(mask, format!("Invalid glob pattern: {}", e)))?
-                .filter_map(Result::ok)
-                .collect();
-
-            if matches.is_empty() {
-                return Err(syn::Error::new_spanned(
-                    mask,
-                    format!("No files matched pattern: {}", mask_value),
-                ));
-            }
-
-            let paths: Vec<ValueWithSpan> = matches
+        SourceType::Generated(_, span) => {
+            return Err(syn::Error::new(*span, "Generated test cases are only produced when the test runs"));
+        }
+        SourceType::PathMask(mask, _) => {
+            let paths: Vec<ValueWithSpan> = glob_files(mask, manifest_dir)?
                 .into_iter()
-                .filter_map(|p| {
-                    let relative_path = p.strip_prefix(&manifest_dir).ok()?;
-                    let path_str = relative_path.to_str()?.to_string();
-                    let path_suffix = path_str
-                        .chars()
-                        .map(|c| {
-                            if c.is_alphanumeric() {
-                                c.to_lowercase().next().unwrap()
-                            } else {
-                                '_'
-                            }
-                        })
-                        .collect::<String>();
-                    
-                    Some(ValueWithSpan {
-                        value: Value::String(path_str),
-                        span: mask.span(),
-                        suffix: Some(format!("pm_{}", path_suffix)),
-                    })
+                .map(|(_, path_str)| ValueWithSpan {
+                    suffix: Some(file_case_suffix(&path_str)),
+                    value: Value::String(path_str),
+                    span: mask.span(),
+                    expected: None,
+                    ignore: None,
+                    should_panic: None,
+                    raw: None,
+                    constants: Vec::new(),
+                    expected_constant: None,
+                    csv_row: None,
                 })
                 .collect();
 
-            let tests_stream = generate_test_set(
-                input_fn,
-                paths,
-                fn_name.clone(),
-                type_name_opt,
-            )?;
-            return Ok(tests_stream);
+            return Ok(LoadedSource {
+                cases: paths,
+                type_name: type_name_opt,
+                files: Vec::new(),
+            });
+        }
+        SourceType::JsonGlob(mask, ty, _) => {
+            let mut cases = Vec::new();
+            let mut files = Vec::new();
+            for (full_path, path_str) in glob_files(mask, manifest_dir)? {
+                let content = std::fs::read_to_string(&full_path).map_err(|e| {
+                    syn::Error::new_spanned(
+                        mask,
+                        format!("Could not read file {}: {}", full_path.display(), e),
+                    )
+                })?;
+                let value: Value = serde_json::from_str(&content).map_err(|e| {
+                    syn::Error::new_spanned(
+                        mask,
+                        format!("Could not parse JSON file {}: {}", path_str, e),
+                    )
+                })?;
+                let case = to_case(input_fn, value, source_span, Some(file_case_suffix(&path_str))).map_err(|e| {
+                    // Name the offending file in the primary message
+                    let mut errors = e.into_iter();
+                    let first = errors.next().unwrap();
+                    let mut err = syn::Error::new(first.span(), format!("{} in file {}", first, path_str));
+                    err.extend(errors);
+                    err
+                })?;
+
+                let file_path_literal = full_path.to_str().ok_or_else(|| {
+                    syn::Error::new_spanned(mask, "Path contains invalid UTF-8")
+                })?;
+                files.push(file_path_literal.to_string());
+
+                cases.push(case);
+            }
+
+            return Ok(LoadedSource {
+                cases,
+                type_name: ty.clone().or(type_name_opt),
+                files,
+            });
         }
     };
 
@@ -165,12 +539,8 @@ pub fn test_params_source(attr: TokenStream, item: TokenStream) -> syn::Result<T
             ));
         }
 
-        if input_fn.sig.inputs.len() == 1 {
-            if let Some(syn::FnArg::Typed(pat_type)) = input_fn.sig.inputs.first() {
-                type_name = Some((*pat_type.ty).clone());
-            }
-        }
         // For multiple parameters, type_name remains None and generate_test_set will infer it as a tuple.
+        type_name = type_name_opt;
     }
 
     let is_vec = if let Some(Type::Path(type_path)) = &type_name {
@@ -179,109 +549,392 @@ pub fn test_params_source(attr: TokenStream, item: TokenStream) -> syn::Result<T
         false
     };
 
-    let type_name_opt = type_name;
+    let files = || file_info.iter().map(|(_, file_path_literal)| file_path_literal.clone()).collect();
 
-    // Parse JSON and generate tests
-    let tests_stream: TokenStream = match serde_json::from_str(&json_content) {
-        Ok(Value::Array(array)) => {
-            // If expected type is Vec, try parsing it as both list of list and just single list before throwing an error
-            if is_vec {
-                let all_are_arrays = array.iter().all(|v| v.is_array());
-                if all_are_arrays {
-                    for v in &array {
-                        check_json_compatibility(&input_fn, v, source_span)?;
-                    }
-                    generate_test_set(
-                        input_fn,
-                        array
-                            .into_iter()
-                            .map(|v| ValueWithSpan {
-                                value: v,
-                                span: source_span,
-                                suffix: None,
-                            })
-                            .collect(),
-                        fn_name.clone(),
-                        type_name_opt,
-                    )?
-                } else {
-                    let val = Value::Array(array);
-                    check_json_compatibility(&input_fn, &val, source_span)?;
-                    // Treat the whole array as a single test case (single list)
-                    generate_test_set(
-                        input_fn,
-                        vec![ValueWithSpan {
-                            value: val,
-                            span: source_span,
-                            suffix: None,
-                        }],
-                        fn_name.clone(),
-                        type_name_opt,
-                    )?
+    // Each row of a CSV file is a test case, remembering the row it was read from
+    if let SourceType::CsvFile(path, _, options, _) = source {
+        let mut cases = Vec::new();
+        for (value, row) in csv_to_values(&content, options, input_fn, path)? {
+            let mut case = to_case(input_fn, value, source_span, None)?;
+            case.csv_row = row;
+            cases.push(case);
+        }
+        return Ok(LoadedSource { cases, type_name, files: files() });
+    }
+
+    // Parse the source and generate tests
+    let parsed = parse_documents(&content, format).map_err(|e| {
+        if let Some((file_path, _)) = &file_info {
+            syn::Error::new_spanned(
+                file_path,
+                format!("Could not parse {} file {}: {}", format.name(), file_path.value(), e),
+            )
+        } else {
+            syn::Error::new(source_span, format!("Could not parse {}: {}", format.name(), e))
+        }
+    })?;
+
+    // Narrow the document down to the selected part before expanding it
+    let parsed = match (parsed, source.select()) {
+        (ParsedSource::Document(document), Some(select)) => ParsedSource::Document(
+            select_pointer(document, &select.value()).map_err(|e| syn::Error::new_spanned(select, e))?,
+        ),
+        (parsed, _) => parsed,
+    };
+
+    let cases = match parsed {
+        ParsedSource::Document(document) => expand_value(input_fn, document, source_span, is_vec)?,
+        ParsedSource::Cases(documents) => {
+            let mut cases = Vec::with_capacity(documents.len());
+            for (document, suffix) in documents {
+                cases.push(to_case(input_fn, document, source_span, suffix)?);
+            }
+            cases
+        }
+    };
+
+    Ok(LoadedSource { cases, type_name, files: files() })
+}
+
+/// Names each test case after the value of one of its fields.
+pub(crate) fn name_cases(cases: &mut [ValueWithSpan], field: &str) -> Result<(), String> {
+    for case in cases {
+        let name = match &case.value {
+            Value::Object(object) => match object.get(field) {
+                Some(Value::Null) | None => {
+                    return Err(format!("Test case has no `{}` field to name it after: {}", field, case.value));
                 }
+                Some(value) => value_to_suffix(value),
+            },
+            other => {
+                return Err(format!("`name_field` requires test cases that are objects, but got: {}", other));
+            }
+        };
+        if name.is_empty() {
+            return Err(format!("Field `{}` of test case is empty: {}", field, case.value));
+        }
+        case.suffix = Some(name);
+    }
+    Ok(())
+}
+
+/// Returns the type of the only parameter of a function.
+fn single_param_type(input_fn: &ItemFn) -> Option<Type> {
+    if input_fn.sig.inputs.len() == 1 {
+        if let Some(syn::FnArg::Typed(pat_type)) = input_fn.sig.inputs.first() {
+            return Some((*pat_type.ty).clone());
+        }
+    }
+    None
+}
+
+/// Text formats that a data source can be written in.
+#[derive(Clone, Copy)]
+enum DataFormat {
+    Json,
+    JsonLines,
+    Yaml,
+    Toml,
+    Csv,
+}
+
+impl DataFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            DataFormat::Json => "JSON",
+            DataFormat::JsonLines => "JSON Lines",
+            DataFormat::Yaml => "YAML",
+            DataFormat::Toml => "TOML",
+            DataFormat::Csv => "CSV",
+        }
+    }
+}
+
+/// Reads a data file relative to `CARGO_MANIFEST_DIR`.
+/// Returns the file content and the absolute path to use with `include_str!`.
+fn read_source_file(path: &LitStr, manifest_dir: Option<&Path>) -> syn::Result<(String, String)> {
+    let file_path_value = path.value();
+    // Resolve the full path
+    let manifest_dir = manifest_dir.ok_or_else(|| {
+        syn::Error::new_spanned(path, "CARGO_MANIFEST_DIR not set")
+    })?;
+    let full_path = manifest_dir.join(&file_path_value);
+    let file_path_literal = full_path.to_str().ok_or_else(|| {
+        syn::Error::new_spanned(path, "Path contains invalid UTF-8")
+    })?;
+
+    // Read the file
+    let content = std::fs::read_to_string(&full_path).map_err(|e| {
+        syn::Error::new_spanned(
+            path,
+            format!("Could not read file {}: {}", full_path.display(), e),
+        )
+    })?;
+    Ok((content, file_path_literal.to_string()))
+}
+
+/// Content of a data source after parsing.
+enum ParsedSource {
+    /// A single document, expanded into test cases by [`expand_value`].
+    Document(Value),
+    /// Documents that are a test case each, with an optional test name suffix.
+    Cases(Vec<(Value, Option<String>)>),
+}
+
+/// Resolves a glob pattern relative to `CARGO_MANIFEST_DIR`.
+/// Returns the matched files as absolute paths and as paths relative to the manifest directory.
+fn glob_files(mask: &LitStr, manifest_dir: Option<&Path>) -> syn::Result<Vec<(PathBuf, String)>> {
+    let mask_value = mask.value();
+    let manifest_dir = manifest_dir.ok_or_else(|| {
+        syn::Error::new_spanned(mask, "CARGO_MANIFEST_DIR not set")
+    })?;
+    let full_mask = manifest_dir.join(&mask_value);
+    let full_mask_str = full_mask.to_str().ok_or_else(|| {
+        syn::Error::new_spanned(mask, "Path mask contains invalid UTF-8")
+    })?;
+
+    let matches: Vec<_> = glob::glob(full_mask_str)
+        .map_err(|e| syn::Error::new_spanned(mask, format!("Invalid glob pattern: {}", e)))?
+        .filter_map(Result::ok)
+        .collect();
+
+    if matches.is_empty() {
+        return Err(syn::Error::new_spanned(
+            mask,
+            format!("No files matched pattern: {}", mask_value),
+        ));
+    }
+
+    Ok(matches
+        .into_iter()
+        .filter_map(|p| {
+            let relative_path = p.strip_prefix(manifest_dir).ok()?;
+            let path_str = relative_path.to_str()?.to_string();
+            Some((p, path_str))
+        })
+        .collect())
+}
+
+/// Turns a file path into a test name suffix.
+fn path_to_suffix(path_str: &str) -> String {
+    path_str
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap()
             } else {
-                for v in &array {
-                    check_json_compatibility(&input_fn, v, source_span)?;
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Names the test generated for a matched file, shared by all sources that generate a test per file.
+fn file_case_suffix(path_str: &str) -> String {
+    format!("pm_{}", path_to_suffix(path_str))
+}
+
+/// Parses the source content.
+///
+/// - A YAML stream with more than one document produces a test case per document.
+/// - A JSON Lines file produces a test case per line, named after the line number.
+///   Blank lines and lines starting with `//` are skipped.
+/// - A TOML document whose only key is an array of tables (`[[case]]`) is unwrapped to that array,
+///   so that each table becomes a separate test case.
+///
+/// CSV files depend on the test signature and are handled by [`csv_to_values`].
+fn parse_documents(content: &str, format: DataFormat) -> Result<ParsedSource, String> {
+    match format {
+        DataFormat::Csv => unreachable!("CSV sources are converted by csv_to_values"),
+        DataFormat::Json => serde_json::from_str(content)
+            .map(ParsedSource::Document)
+            .map_err(|e| e.to_string()),
+        DataFormat::JsonLines => {
+            let mut cases = Vec::new();
+            for (i, line) in content.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with("//") {
+                    continue;
                 }
-                generate_test_set(
-                    input_fn,
-                    array
-                        .into_iter()
-                        .map(|v| ValueWithSpan {
-                            value: v,
-                            span: source_span,
-                            suffix: None,
-                        })
-                        .collect(),
-                    fn_name.clone(),
-                    type_name_opt,
-                )?
+                let value = serde_json::from_str(line).map_err(|e| {
+                    let message = e.to_string();
+                    let message = message
+                        .strip_suffix(&format!(" at line {} column {}", e.line(), e.column()))
+                        .unwrap_or(&message);
+                    format!("{} at line {} column {}", message, i + 1, e.column())
+                })?;
+                cases.push((value, Some(format!("line_{}", i + 1))));
+            }
+            if cases.is_empty() {
+                return Err("no lines found".to_string());
             }
+            Ok(ParsedSource::Cases(cases))
         }
-        Ok(single_value) => {
-            check_json_compatibility(&input_fn, &single_value, source_span)?;
-            if is_vec {
-                return Err(syn::Error::new(
-                    source_span,
-                    format!("Expected JSON array for Vec type, but got: {}", single_value),
-                ));
+        DataFormat::Yaml => {
+            let mut documents = Vec::new();
+            for document in serde_yaml::Deserializer::from_str(content) {
+                documents.push(Value::deserialize(document).map_err(|e| e.to_string())?);
             }
-            generate_test_set(
-                input_fn,
-                vec![ValueWithSpan {
-                    value: single_value,
-                    span: source_span,
-                    suffix: None,
-                }],
-                fn_name.clone(),
-                type_name_opt,
-            )?
-        }
-        Err(e) => {
-            if let Some((file_path, _)) = &file_info {
-                return Err(syn::Error::new_spanned(
-                    file_path,
-                    format!("Could not parse JSON file {}: {}", file_path.value(), e),
+            match documents.len() {
+                0 => Err("no documents found".to_string()),
+                1 => Ok(ParsedSource::Document(documents.pop().unwrap())),
+                _ => Ok(ParsedSource::Cases(documents.into_iter().map(|d| (d, None)).collect())),
+            }
+        }
+        DataFormat::Toml => {
+            let value: Value = toml::from_str(content).map_err(|e| {
+                if let Some(span) = e.span() {
+                    let (line, column) = line_and_column(content, span.start);
+                    format!("{} at line {} column {}", e.message().trim_end(), line, column)
+                } else {
+                    e.message().trim_end().to_string()
+                }
+            })?;
+            match value {
+                Value::Object(table)
+                    if table.len() == 1
+                        && table
+                            .values()
+                            .all(|v| v.as_array().is_some_and(|a| a.iter().all(Value::is_object))) =>
+                {
+                    Ok(ParsedSource::Document(table.into_iter().next().unwrap().1))
+                }
+                other => Ok(ParsedSource::Document(other)),
+            }
+        }
+    }
+}
+
+/// Selects a part of a document with a JSON Pointer (RFC 6901), e.g. `/suites/login/cases`.
+fn select_pointer(mut value: Value, pointer: &str) -> Result<Value, String> {
+    if pointer.is_empty() {
+        return Ok(value);
+    }
+    if !pointer.starts_with('/') {
+        return Err(format!("Expected a JSON Pointer starting with `/`, but got: `{}`", pointer));
+    }
+
+    for raw_segment in pointer[1..].split('/') {
+        let segment = raw_segment.replace("~1", "/").replace("~0", "~");
+        value = match value {
+            Value::Object(mut object) => match object.remove(&segment) {
+                Some(v) => v,
+                None => {
+                    let keys = object.keys().map(|k| format!("`{}`", k)).collect::<Vec<_>>().join(", ");
+                    return Err(format!(
+                        "Segment `{}` of `{}` did not resolve: no such key, available keys: [{}]",
+                        raw_segment, pointer, keys
+                    ));
+                }
+            },
+            Value::Array(mut array) => {
+                let len = array.len();
+                match segment.parse::<usize>() {
+                    Ok(index) if index < len => array.swap_remove(index),
+                    Ok(index) => {
+                        return Err(format!(
+                            "Segment `{}` of `{}` did not resolve: index {} is out of bounds for array of length {}",
+                            raw_segment, pointer, index, len
+                        ));
+                    }
+                    Err(_) => {
+                        return Err(format!(
+                            "Segment `{}` of `{}` did not resolve: expected an array index",
+                            raw_segment, pointer
+                        ));
+                    }
+                }
+            }
+            other => {
+                let kind = match other {
+                    Value::Null => "null",
+                    Value::Bool(_) => "a boolean",
+                    Value::Number(_) => "a number",
+                    _ => "a string",
+                };
+                return Err(format!(
+                    "Segment `{}` of `{}` did not resolve: cannot index into {}",
+                    raw_segment, pointer, kind
                 ));
+            }
+        };
+    }
+    Ok(value)
+}
+
+/// Converts a byte offset into a 1-based line and column.
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
+
+/// Expands a parsed document into test cases.
+///
+/// A top-level array produces one test per element, unless the parameter is a `Vec`
+/// and the elements are not arrays themselves, in which case the whole array is a single case.
+/// Any other value produces a single test.
+fn expand_value(
+    input_fn: &ItemFn,
+    value: Value,
+    source_span: Span,
+    is_vec: bool,
+) -> syn::Result<Vec<ValueWithSpan>> {
+    match value {
+        Value::Array(array) => {
+            // Elements carrying an expected result are test cases even for a Vec parameter
+            let has_expected = return_type(input_fn).is_some()
+                && array.iter().any(|v| v.as_object().is_some_and(|o| o.contains_key("expected")));
+            // If expected type is Vec, try parsing it as both list of list and just single list before throwing an error
+            if is_vec && !has_expected && !array.iter().all(|v| v.is_array()) {
+                // Treat the whole array as a single test case (single list)
+                Ok(vec![to_case(input_fn, Value::Array(array), source_span, None)?])
             } else {
+                array
+                    .into_iter()
+                    .map(|v| to_case(input_fn, v, source_span, None))
+                    .collect()
+            }
+        }
+        single_value => {
+            let case = to_case(input_fn, single_value, source_span, None)?;
+            if is_vec && !case.value.is_array() {
                 return Err(syn::Error::new(
                     source_span,
-                    format!("Could not parse JSON: {}", e),
+                    format!("Expected JSON array for Vec type, but got: {}", case.value),
                 ));
             }
+            Ok(vec![case])
         }
-    };
+    }
+}
 
-    if let Some((_file_path, file_path_literal)) = file_info {
-        let const_name = format_ident!("{}_DATA", fn_name.to_string().to_uppercase());
-        let file_path_const = quote! { const #const_name: &str = include_str!(#file_path_literal); };
-        // Output the const + generated tests
-        Ok(quote! {
-            /// --- Test data source
-            #file_path_const
-            #tests_stream
-        })
-    } else {
-        Ok(tests_stream)
+/// Turns a value read from a data source into a test case for `input_fn`.
+///
+/// The `$ignore` and `$should_panic` markers are taken from the test case data, see [`split_markers`],
+/// and if the function returns a value, so is the expected result, see [`split_expected`].
+fn to_case(input_fn: &ItemFn, value: Value, span: Span, suffix: Option<String>) -> syn::Result<ValueWithSpan> {
+    let (value, ignore, should_panic) = split_markers(value).map_err(|e| syn::Error::new(span, e))?;
+    let (value, expected) = match return_type(input_fn) {
+        Some(_) => split_expected(value),
+        None => (value, None),
+    };
+    check_json_compatibility(input_fn, &value, span)?;
+    if let (Some(expected), Some(return_type)) = (&expected, return_type(input_fn)) {
+        validate_type_match(return_type, expected, span)
+            .map_err(|e| syn::Error::new(span, format!("Invalid expected value: {}", e)))?;
     }
+    Ok(ValueWithSpan {
+        value,
+        span,
+        suffix,
+        expected,
+        ignore,
+        should_panic,
+        raw: None,
+        constants: Vec::new(),
+        expected_constant: None,
+        csv_row: None,
+    })
 }
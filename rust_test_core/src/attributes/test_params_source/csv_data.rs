@@ -0,0 +1,242 @@
+use crate::attributes::common::validate_type_match;
+use crate::attributes::test_params_source::source_type::CsvOptions;
+use serde_json::Value;
+use syn::{GenericArgument, ItemFn, LitStr, PathArguments, Type};
+
+/// Value kind shared by all non-empty cells of a CSV column.
+#[derive(Clone, Copy, PartialEq)]
+enum ColumnKind {
+    Integer,
+    Float,
+    Bool,
+    String,
+}
+
+/// Converts the rows of a CSV file into test case values.
+///
+/// - For multiple parameters each row becomes an array of its cells in column order.
+/// - For a single parameter of a built-in type the file must have exactly one column.
+/// - Otherwise each row becomes an object keyed by the header row.
+///
+/// Positional cells are coerced to their parameter type and validated against it.
+/// The macro can't see the definition of a struct, so the cells of an object row are coerced
+/// to the kind shared by the whole column, with empty cells as `null`, and the row they came
+/// from is returned beside them. The generated test deserializes such a row field by field
+/// with [`from_csv_row`](crate::runtime::from_csv_row), which checks each cell against the
+/// type of its field.
+pub(crate) fn csv_to_values(
+    content: &str,
+    options: &CsvOptions,
+    input_fn: &ItemFn,
+    path: &LitStr,
+) -> syn::Result<Vec<(Value, Option<usize>)>> {
+    let parse_error = |e: csv::Error| {
+        syn::Error::new_spanned(path, format!("Could not parse CSV file {}: {}", path.value(), e))
+    };
+
+    let mut builder = csv::ReaderBuilder::new();
+    if let Some(delimiter) = &options.delimiter {
+        builder.delimiter(delimiter.value() as u8);
+    }
+    if let Some(quote) = &options.quote {
+        builder.quote(quote.value() as u8);
+    }
+    let mut reader = builder.from_reader(content.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(parse_error)?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+    let records = reader
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(parse_error)?;
+
+    let column_kinds: Vec<ColumnKind> = (0..headers.len())
+        .map(|i| infer_column_kind(records.iter().filter_map(|r| r.get(i))))
+        .collect();
+
+    let param_types = param_types(input_fn);
+    let positional = has_positional_cells(input_fn);
+    let mut values = Vec::with_capacity(records.len());
+    for record in &records {
+        let row = record.position().map_or(0, |p| p.line());
+        if positional {
+            if record.len() != param_types.len() {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    format!(
+                        "CSV row {}: expected {} column(s) for {} parameter(s), but got {}",
+                        row,
+                        param_types.len(),
+                        param_types.len(),
+                        record.len()
+                    ),
+                ));
+            }
+            let mut cells = Vec::with_capacity(record.len());
+            for (i, (cell, ty)) in record.iter().zip(&param_types).enumerate() {
+                let value = coerce_typed(ty, cell, column_kinds[i]);
+                validate_type_match(ty, &value, path.span()).map_err(|e| {
+                    syn::Error::new_spanned(path, format!("CSV row {}, column `{}`: {}", row, headers[i], e))
+                })?;
+                cells.push(value);
+            }
+            if cells.len() == 1 {
+                values.push((cells.pop().unwrap(), None));
+            } else {
+                values.push((Value::Array(cells), None));
+            }
+        } else {
+            let mut object = serde_json::Map::new();
+            for (i, cell) in record.iter().enumerate() {
+                let header = headers.get(i).ok_or_else(|| {
+                    syn::Error::new_spanned(path, format!("CSV row {}: column {} has no header", row, i + 1))
+                })?;
+                object.insert(header.clone(), coerce(cell, column_kinds[i]));
+            }
+            values.push((Value::Object(object), Some(row as usize)));
+        }
+    }
+
+    Ok(values)
+}
+
+/// Whether the columns of a CSV file map to the parameters of `input_fn` in order,
+/// rather than to the fields of a struct.
+pub(crate) fn has_positional_cells(input_fn: &ItemFn) -> bool {
+    let param_types = param_types(input_fn);
+    param_types.len() > 1 || param_types.first().is_some_and(|ty| is_scalar_type(ty))
+}
+
+fn param_types(input_fn: &ItemFn) -> Vec<&Type> {
+    input_fn
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::FnArg::Typed(pat_type) => Some(&*pat_type.ty),
+            _ => None,
+        })
+        .collect()
+}
+
+fn infer_column_kind<'a>(cells: impl Iterator<Item = &'a str>) -> ColumnKind {
+    let mut kind = None;
+    for cell in cells.map(str::trim).filter(|c| !c.is_empty()) {
+        let cell_kind = if parse_integer(cell).is_some() {
+            ColumnKind::Integer
+        } else if parse_float(cell).is_some() {
+            ColumnKind::Float
+        } else if parse_bool(cell).is_some() {
+            ColumnKind::Bool
+        } else {
+            ColumnKind::String
+        };
+        kind = match (kind, cell_kind) {
+            (None, k) => Some(k),
+            (Some(a), b) if a == b => Some(a),
+            (Some(ColumnKind::Integer), ColumnKind::Float) | (Some(ColumnKind::Float), ColumnKind::Integer) => {
+                Some(ColumnKind::Float)
+            }
+            _ => return ColumnKind::String,
+        };
+    }
+    kind.unwrap_or(ColumnKind::String)
+}
+
+fn coerce(cell: &str, kind: ColumnKind) -> Value {
+    let trimmed = cell.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    match kind {
+        ColumnKind::Integer => parse_integer(trimmed),
+        ColumnKind::Float => parse_float(trimmed),
+        ColumnKind::Bool => parse_bool(trimmed),
+        ColumnKind::String => None,
+    }
+    .unwrap_or_else(|| Value::String(cell.to_string()))
+}
+
+fn coerce_typed(ty: &Type, cell: &str, kind: ColumnKind) -> Value {
+    let trimmed = cell.trim();
+    match type_name(ty).as_deref() {
+        Some("Option") => match option_inner_type(ty) {
+            Some(_) if trimmed.is_empty() => Value::Null,
+            Some(inner) => coerce_typed(inner, cell, kind),
+            None => coerce(cell, kind),
+        },
+        Some("u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize") => {
+            parse_integer(trimmed).unwrap_or_else(|| Value::String(cell.to_string()))
+        }
+        Some("f32" | "f64") => parse_float(trimmed).unwrap_or_else(|| Value::String(cell.to_string())),
+        Some("bool") => parse_bool(trimmed).unwrap_or_else(|| Value::String(cell.to_string())),
+        Some("String" | "str" | "char") => Value::String(cell.to_string()),
+        _ => coerce(cell, kind),
+    }
+}
+
+fn parse_integer(s: &str) -> Option<Value> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    // Leading zeros usually mean an identifier (zip code, account number), not a number
+    if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // Integers of any size are kept exact, like the integers of the other sources
+    if let Ok(i) = s.parse::<i128>() {
+        serde_json::Number::from_i128(i).map(Value::Number)
+    } else {
+        s.parse::<u128>().ok().and_then(serde_json::Number::from_u128).map(Value::Number)
+    }
+}
+
+fn parse_float(s: &str) -> Option<Value> {
+    if !s.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let number = s.parse::<f64>().ok().and_then(serde_json::Number::from_f64)?;
+    // Keep the digits as written, `1.50` stays `1.50` for a struct field that is a string
+    Some(Value::Number(s.parse().unwrap_or(number)))
+}
+
+fn parse_bool(s: &str) -> Option<Value> {
+    match s.to_ascii_lowercase().as_str() {
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        _ => None,
+    }
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(tp) => tp.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(tr) => type_name(&tr.elem),
+        _ => None,
+    }
+}
+
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    if let Type::Path(tp) = ty {
+        if let Some(PathArguments::AngleBracketed(args)) = tp.path.segments.last().map(|s| &s.arguments) {
+            if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                return Some(inner);
+            }
+        }
+    }
+    None
+}
+
+/// Built-in types that take a single CSV cell rather than a whole row.
+fn is_scalar_type(ty: &Type) -> bool {
+    match type_name(ty).as_deref() {
+        Some("Option") => option_inner_type(ty).is_some_and(is_scalar_type),
+        Some(
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize"
+            | "f32" | "f64" | "bool" | "char" | "String" | "str",
+        ) => true,
+        _ => false,
+    }
+}
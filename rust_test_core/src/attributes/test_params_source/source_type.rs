@@ -1,4 +1,4 @@
-use syn::{LitStr, Type, Path, Token};
+use syn::{Expr, LitChar, LitInt, LitStr, Type, Path, Token};
 use syn::parse::{Parse, ParseStream};
 use proc_macro2::Span;
 use syn::spanned::Spanned;
@@ -6,27 +6,118 @@ use syn::spanned::Spanned;
 /// A source type to generate tests from.
 ///
 /// # Variants
-/// - `SourceType::JsonFile(LitStr, Type, Span)` — pass a path to a JSON file and a type
-/// to deserialize it into.`
+/// - `SourceType::JsonFile(LitStr, Type, LitStr, Span)` — pass a path to a JSON file, a type
+/// to deserialize it into and an optional JSON Pointer to select the cases with.
+/// - `SourceType::JsonLines(LitStr, Type, Span)` — a newline-delimited JSON file, one case per line.
+/// - `SourceType::JsonGlob(LitStr, Type, Span)` — a glob pattern, one case per matched JSON file.
+/// - `SourceType::YamlFile(LitStr, Type, Span)` — same as `JsonFile`, but for YAML files.
+/// - `SourceType::YamlString(LitStr, Type, Span)` — an inline YAML string literal.
+/// - `SourceType::TomlFile(LitStr, Type, Span)` — a TOML file, an array of tables is a list of cases.
+/// - `SourceType::CsvFile(LitStr, Type, CsvOptions, Span)` — a CSV file with a header row, one case per row.
+/// - `SourceType::Generated(GeneratedOptions, Span)` — random inputs produced when the test runs.
 #[allow(dead_code)]
 pub enum SourceType {
-    JsonFile(LitStr, Option<Type>, Span),
-    JsonString(LitStr, Option<Type>, Span),
-    JsonResponse(LitStr, Option<Type>, Span),
+    JsonFile(LitStr, Option<Type>, Option<LitStr>, Span),
+    JsonString(LitStr, Option<Type>, Option<LitStr>, Span),
+    JsonResponse(LitStr, Option<Type>, Option<LitStr>, Span),
+    JsonLines(LitStr, Option<Type>, Span),
+    JsonGlob(LitStr, Option<Type>, Span),
+    YamlFile(LitStr, Option<Type>, Span),
+    YamlString(LitStr, Option<Type>, Span),
+    TomlFile(LitStr, Option<Type>, Span),
+    CsvFile(LitStr, Option<Type>, CsvOptions, Span),
     PathMask(LitStr, Span),
+    Generated(GeneratedOptions, Span),
+}
+
+/// Reader options of `SourceType::CsvFile`, e.g. `CsvFile("data.csv", delimiter = ';', quote = '\'')`.
+#[derive(Default)]
+pub struct CsvOptions {
+    pub delimiter: Option<LitChar>,
+    pub quote: Option<LitChar>,
+}
+
+/// Options of `SourceType::Generated`, e.g. `Generated(strategy = 0..100u32, cases = 256, seed = 42)`.
+#[derive(Default)]
+pub struct GeneratedOptions {
+    pub strategy: Option<Expr>,
+    pub cases: Option<LitInt>,
+    pub seed: Option<LitInt>,
 }
 
 impl SourceType {
     pub fn span(&self) -> Span {
         match self {
-            SourceType::JsonFile(_, _, span) => *span,
-            SourceType::JsonString(_, _, span) => *span,
-            SourceType::JsonResponse(_, _, span) => *span,
+            SourceType::JsonFile(_, _, _, span) => *span,
+            SourceType::JsonString(_, _, _, span) => *span,
+            SourceType::JsonResponse(_, _, _, span) => *span,
+            SourceType::JsonLines(_, _, span) => *span,
+            SourceType::JsonGlob(_, _, span) => *span,
+            SourceType::YamlFile(_, _, span) => *span,
+            SourceType::YamlString(_, _, span) => *span,
+            SourceType::TomlFile(_, _, span) => *span,
+            SourceType::CsvFile(_, _, _, span) => *span,
             SourceType::PathMask(_, span) => *span,
+            SourceType::Generated(_, span) => *span,
+        }
+    }
+}
+
+impl SourceType {
+    /// The JSON Pointer given with `select = "..."`, if any.
+    pub fn select(&self) -> Option<&LitStr> {
+        match self {
+            SourceType::JsonFile(_, _, select, _)
+            | SourceType::JsonString(_, _, select, _)
+            | SourceType::JsonResponse(_, _, select, _) => select.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The type given in the attribute to deserialize the data into, if any.
+    pub fn type_name(&self) -> Option<&Type> {
+        match self {
+            SourceType::JsonFile(_, ty, _, _)
+            | SourceType::JsonString(_, ty, _, _)
+            | SourceType::JsonResponse(_, ty, _, _)
+            | SourceType::CsvFile(_, ty, _, _) => ty.as_ref(),
+            SourceType::JsonLines(_, ty, _)
+            | SourceType::JsonGlob(_, ty, _)
+            | SourceType::YamlFile(_, ty, _)
+            | SourceType::YamlString(_, ty, _)
+            | SourceType::TomlFile(_, ty, _) => ty.as_ref(),
+            SourceType::PathMask(_, _) | SourceType::Generated(_, _) => None,
         }
     }
 }
 
+/// Parses the arguments of the variants reading a string literal: the literal, then an optional type
+/// and, for the JSON variants, an optional `select = "/json/pointer"` in any order.
+fn parse_source_args(content: ParseStream, with_select: bool) -> syn::Result<(LitStr, Option<Type>, Option<LitStr>)> {
+    // Parse the literal (Required)
+    let literal: LitStr = content.parse()?;
+
+    let mut arg_type: Option<Type> = None;
+    let mut select: Option<LitStr> = None;
+    while content.peek(Token![,]) {
+        content.parse::<Token![,]>()?;
+        if content.is_empty() {
+            break;
+        }
+        if content.peek(syn::Ident) && content.peek2(Token![=]) {
+            let option: syn::Ident = content.parse()?;
+            content.parse::<Token![=]>()?;
+            match option.to_string().as_str() {
+                "select" if with_select => select = Some(content.parse()?),
+                o => return Err(syn::Error::new_spanned(&option, format!("Unknown option: {}", o))),
+            }
+        } else {
+            arg_type = Some(content.parse()?);
+        }
+    }
+    Ok((literal, arg_type, select))
+}
+
 impl Parse for SourceType {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         // 1. Parse the path (e.g., SourceType::<User>::JsonFile or JsonFile)
@@ -60,59 +151,135 @@ impl Parse for SourceType {
             "JsonFile" => {
                 let content;
                 syn::parenthesized!(content in input);
-                
-                // Parse the path (Required)
-                let file_path: LitStr = content.parse()?;
-                
-                // Parse the type if it follows a comma: ("path", User)
-                let mut arg_type: Option<Type> = None;
-                if content.peek(Token![,]) {
-                    content.parse::<Token![,]>()?;
-                    arg_type = Some(content.parse()?);
-                }
+
+                // ("path", User, select = "/pointer")
+                let (file_path, arg_type, select) = parse_source_args(&content, true)?;
 
                 // Preference: argument type > turbofish type
                 let final_type = arg_type.or(generic_type);
 
-                Ok(SourceType::JsonFile(file_path, final_type, path_span))
+                Ok(SourceType::JsonFile(file_path, final_type, select, path_span))
             }
             "JsonString" => {
                 let content;
                 syn::parenthesized!(content in input);
-                
-                // Parse the JSON string (Required)
-                let json_string: LitStr = content.parse()?;
-                
-                // Parse the type if it follows a comma: ("{}", User)
-                let mut arg_type: Option<Type> = None;
-                if content.peek(Token![,]) {
-                    content.parse::<Token![,]>()?;
-                    arg_type = Some(content.parse()?);
-                }
+
+                // ("{}", User, select = "/pointer")
+                let (json_string, arg_type, select) = parse_source_args(&content, true)?;
 
                 // Preference: argument type > turbofish type
                 let final_type = arg_type.or(generic_type);
 
-                Ok(SourceType::JsonString(json_string, final_type, path_span))
+                Ok(SourceType::JsonString(json_string, final_type, select, path_span))
             }
             "JsonResponse" => {
                 let content;
                 syn::parenthesized!(content in input);
 
-                // Parse the URL (Required)
-                let url: LitStr = content.parse()?;
+                // ("url", User, select = "/pointer")
+                let (url, arg_type, select) = parse_source_args(&content, true)?;
+
+                // Preference: argument type > turbofish type
+                let final_type = arg_type.or(generic_type);
+
+                Ok(SourceType::JsonResponse(url, final_type, select, path_span))
+            }
+            "JsonLines" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                // ("path", User)
+                let (file_path, arg_type, _) = parse_source_args(&content, false)?;
 
-                // Parse the type if it follows a comma: ("url", User)
+                // Preference: argument type > turbofish type
+                let final_type = arg_type.or(generic_type);
+
+                Ok(SourceType::JsonLines(file_path, final_type, path_span))
+            }
+            "JsonGlob" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                // ("pattern", User)
+                let (path_mask, arg_type, _) = parse_source_args(&content, false)?;
+
+                // Preference: argument type > turbofish type
+                let final_type = arg_type.or(generic_type);
+
+                Ok(SourceType::JsonGlob(path_mask, final_type, path_span))
+            }
+            "YamlFile" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                // ("path", User)
+                let (file_path, arg_type, _) = parse_source_args(&content, false)?;
+
+                // Preference: argument type > turbofish type
+                let final_type = arg_type.or(generic_type);
+
+                Ok(SourceType::YamlFile(file_path, final_type, path_span))
+            }
+            "YamlString" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                // ("a: 1", User)
+                let (yaml_string, arg_type, _) = parse_source_args(&content, false)?;
+
+                // Preference: argument type > turbofish type
+                let final_type = arg_type.or(generic_type);
+
+                Ok(SourceType::YamlString(yaml_string, final_type, path_span))
+            }
+            "TomlFile" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                // ("path", User)
+                let (file_path, arg_type, _) = parse_source_args(&content, false)?;
+
+                // Preference: argument type > turbofish type
+                let final_type = arg_type.or(generic_type);
+
+                Ok(SourceType::TomlFile(file_path, final_type, path_span))
+            }
+            "CsvFile" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                // Parse the path (Required)
+                let file_path: LitStr = content.parse()?;
+
+                // Parse the type and reader options: ("path", User, delimiter = ';')
                 let mut arg_type: Option<Type> = None;
-                if content.peek(Token![,]) {
+                let mut options = CsvOptions::default();
+                while content.peek(Token![,]) {
                     content.parse::<Token![,]>()?;
-                    arg_type = Some(content.parse()?);
+                    if content.is_empty() {
+                        break;
+                    }
+                    if content.peek(syn::Ident) && content.peek2(Token![=]) {
+                        let option: syn::Ident = content.parse()?;
+                        content.parse::<Token![=]>()?;
+                        let value: LitChar = content.parse()?;
+                        if !value.value().is_ascii() {
+                            return Err(syn::Error::new_spanned(&value, "Expected an ASCII character"));
+                        }
+                        match option.to_string().as_str() {
+                            "delimiter" => options.delimiter = Some(value),
+                            "quote" => options.quote = Some(value),
+                            o => return Err(syn::Error::new_spanned(&option, format!("Unknown CsvFile option: {}", o))),
+                        }
+                    } else {
+                        arg_type = Some(content.parse()?);
+                    }
                 }
 
                 // Preference: argument type > turbofish type
                 let final_type = arg_type.or(generic_type);
 
-                Ok(SourceType::JsonResponse(url, final_type, path_span))
+                Ok(SourceType::CsvFile(file_path, final_type, options, path_span))
             }
             "PathMask" => {
                 let content;
@@ -123,6 +290,49 @@ impl Parse for SourceType {
 
                 Ok(SourceType::PathMask(path_mask, path_span))
             }
+            "Generated" => {
+                if let Some(ty) = generic_type {
+                    return Err(syn::Error::new_spanned(
+                        ty,
+                        "Generated takes the types of the test function parameters, use `strategy = ...` to change the values",
+                    ));
+                }
+
+                // The options are optional: `Generated` is the same as `Generated()`
+                let mut options = GeneratedOptions::default();
+                if input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in input);
+
+                    // (strategy = 0..10u32, cases = 100, seed = 42)
+                    while !content.is_empty() {
+                        let option: syn::Ident = content.parse()?;
+                        content.parse::<Token![=]>()?;
+                        match option.to_string().as_str() {
+                            "strategy" => options.strategy = Some(content.parse()?),
+                            "cases" => {
+                                let cases: LitInt = content.parse()?;
+                                if cases.base10_parse::<u32>()? == 0 {
+                                    return Err(syn::Error::new_spanned(&cases, "Expected at least one case"));
+                                }
+                                options.cases = Some(cases);
+                            }
+                            "seed" => {
+                                let seed: LitInt = content.parse()?;
+                                seed.base10_parse::<u64>()?;
+                                options.seed = Some(seed);
+                            }
+                            o => return Err(syn::Error::new_spanned(&option, format!("Unknown Generated option: {}", o))),
+                        }
+                        if content.is_empty() {
+                            break;
+                        }
+                        content.parse::<Token![,]>()?;
+                    }
+                }
+
+                Ok(SourceType::Generated(options, path_span))
+            }
             v => Err(syn::Error::new_spanned(last_segment, format!("Unknown variant: {}", v))),
         }
     }
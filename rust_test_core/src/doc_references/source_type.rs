@@ -6,11 +6,22 @@ use serde::de::DeserializeOwned;
 /// - `T`: The type to deserialize into. If omitted, the macro attempts to
 /// infer it from the function signature.
 ///
+/// `JsonFile`, `JsonString` and `JsonResponse` accept an optional JSON Pointer to select
+/// the cases from a larger document before they are expanded into tests:
+/// `JsonFile("big.json", select = "/suites/login/cases")`.
+///
 /// # Variants:
 /// - [`JsonFile::<T>(path)`](SourceType::JsonFile): A path to a JSON file.
 /// - [`JsonString::<T>(json)`](SourceType::JsonString): A JSON string literal.
 /// - [`JsonResponse::<T>(url)`](SourceType::JsonResponse): A URL to fetch JSON from.
+/// - [`JsonLines::<T>(path)`](SourceType::JsonLines): A path to a newline-delimited JSON file.
+/// - [`JsonGlob::<T>(pattern)`](SourceType::JsonGlob): A glob pattern to match JSON files.
+/// - [`YamlFile::<T>(path)`](SourceType::YamlFile): A path to a YAML file.
+/// - [`YamlString::<T>(yaml)`](SourceType::YamlString): A YAML string literal.
+/// - [`TomlFile::<T>(path)`](SourceType::TomlFile): A path to a TOML file.
+/// - [`CsvFile::<T>(path)`](SourceType::CsvFile): A path to a CSV file with a header row.
 /// - [`PathMask(pattern)`](SourceType::PathMask): A glob pattern to match files.
+/// - [`Generated(strategy = ..., cases = ..., seed = ...)`](SourceType::Generated): Random inputs.
 pub enum SourceType<T: DeserializeOwned>
 {
     /// # Example
@@ -67,6 +78,128 @@ pub enum SourceType<T: DeserializeOwned>
     /// ```
     JsonResponse(&'static str),
 
+    /// A path to a newline-delimited JSON (NDJSON) file.
+    ///
+    /// It generates a test for each line, named after the line number (`test_name__line_3`).
+    /// Blank lines and lines starting with `//` are skipped.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rust_test_core::SourceType;
+    /// # use serde::Deserialize;
+    /// # #[derive(Deserialize)]
+    /// # struct Request { method: String, path: String }
+    /// # let source: SourceType<Request> =
+    /// SourceType::JsonLines("corpus.ndjson")
+    /// # ;
+    /// ```
+    JsonLines(&'static str),
+
+    /// A glob pattern to match JSON files.
+    ///
+    /// It generates a test for each matched file, deserialized into the parameter type.
+    /// Tests are named after the file, e.g. `test_name__pm_tests_cases_login_json`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rust_test_core::SourceType;
+    /// # use serde::Deserialize;
+    /// # #[derive(Deserialize)]
+    /// # struct Case { input: String }
+    /// # let source: SourceType<Case> =
+    /// SourceType::JsonGlob("tests/cases/**/*.json")
+    /// # ;
+    /// ```
+    JsonGlob(&'static str),
+
+    /// A path to a YAML file.
+    ///
+    /// Follows the same rules as [`JsonFile`](SourceType::JsonFile).
+    /// A multi-document stream (documents separated by `---`) generates one test per document.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rust_test_core::SourceType;
+    /// # use serde::Deserialize;
+    /// # #[derive(Deserialize)]
+    /// # struct User { name: String, age: u32 }
+    /// # let user_source: SourceType<User> =
+    /// // Type inferred from the function signature:
+    /// SourceType::YamlFile("data.yaml")
+    /// # ;
+    /// # let users_source =
+    /// // Type explicitly provided,
+    /// // can also be used as YamlFile::<Vec<User>>("data.yaml")
+    /// SourceType::<Vec<User>>::YamlFile("data.yaml")
+    /// # ;
+    /// ```
+    YamlFile(&'static str),
+
+    /// # Example
+    /// ```rust
+    /// # use rust_test_core::SourceType;
+    /// # use serde::Deserialize;
+    /// # #[derive(Deserialize)]
+    /// # struct User { name: String, age: u32 }
+    /// # let user_source: SourceType<User> =
+    /// // Type inferred from the function signature:
+    /// SourceType::YamlString(r#"
+    /// name: Alice
+    /// age: 30
+    /// "#)
+    /// # ;
+    /// ```
+    YamlString(&'static str),
+
+    /// A path to a TOML file.
+    ///
+    /// If the only top-level key of the document is an array of tables (`[[case]]`),
+    /// a test is generated for each table. Otherwise the whole document is a single test case.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rust_test_core::SourceType;
+    /// # use serde::Deserialize;
+    /// # #[derive(Deserialize)]
+    /// # struct Case { a: i32, b: i32, expected: i32 }
+    /// # let source: SourceType<Case> =
+    /// // cases.toml:
+    /// // [[case]]
+    /// // a = 1
+    /// // b = 2
+    /// // expected = 3
+    /// SourceType::TomlFile("cases.toml")
+    /// # ;
+    /// ```
+    TomlFile(&'static str),
+
+    /// A path to a CSV file with a header row.
+    ///
+    /// It generates a test for each row:
+    /// - for a single struct parameter, the header row maps to the struct fields;
+    /// - for multiple parameters, the columns map to the parameters in order;
+    /// - for a single parameter of a built-in type, the file must have exactly one column.
+    ///
+    /// Positional cells are converted by the parameter type and checked at compile time.
+    /// Struct fields are converted by their field type when the row is deserialized, since the
+    /// macro can't see the struct: a cell that doesn't fit its field fails the generated test
+    /// with the row and column. Empty cells become `None`, or `""` for a `String`.
+    /// Use `delimiter = ';'` and `quote = '\''` to change the reader options.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rust_test_core::SourceType;
+    /// # use serde::Deserialize;
+    /// # #[derive(Deserialize)]
+    /// # struct User { name: String, age: u32 }
+    /// # let user_source: SourceType<User> =
+    /// SourceType::CsvFile("users.csv")
+    /// # ;
+    /// // With reader options:
+    /// // CsvFile("users.csv", delimiter = ';', quote = '\'')
+    /// ```
+    CsvFile(&'static str),
+
     /// A glob pattern to match files.
     ///
     /// It generates a test for each file matching the pattern.
@@ -82,6 +215,29 @@ pub enum SourceType<T: DeserializeOwned>
     /// ```
     PathMask(&'static str),
 
+    /// Random inputs produced when the test runs, see [`generated`](crate::generated).
+    ///
+    /// A single test named `<function>__generated` runs the function with `cases` inputs
+    /// (256 by default). All options are optional:
+    /// - `strategy`: a [`Strategy`](crate::generated::Strategy) of the parameter type, or of a tuple
+    ///   of the parameter types for several parameters. Defaults to [`any`](crate::generated::any).
+    /// - `cases`: the number of inputs to run.
+    /// - `seed`: the seed of the random inputs; a new one is picked for every run if omitted.
+    ///
+    /// The first failing input is shrunk to a minimal counterexample, which is reported with
+    /// the seed. Set the `RUST_TEST_SEED` environment variable to replay a run with that seed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rust_test_core::SourceType;
+    /// # let source: SourceType<()> =
+    /// SourceType::Generated
+    /// # ;
+    /// // With options:
+    /// // Generated(strategy = (0..100u32, any::<bool>()), cases = 1000, seed = 42)
+    /// ```
+    Generated,
+
     #[doc(hidden)]
     __PrivateMarker(PhantomData<T>)
 }
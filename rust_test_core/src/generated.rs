@@ -0,0 +1,564 @@
+//! Random inputs for [`SourceType::Generated`](crate::SourceType::Generated).
+//!
+//! A [`Strategy`] produces random values of a type and knows how to make a value simpler.
+//! When a generated case fails, [`run`] keeps replacing the input with the first simpler
+//! variant that still fails, and reports the smallest failing input it reached.
+//!
+//! Built-in strategies:
+//! - [`any::<T>()`](any) for integers, floats, `bool`, `char`, `String`, `Vec<T>`, `Option<T>` and tuples;
+//! - integer, float and `char` ranges, e.g. `0..100u32` or `'a'..='z'`;
+//! - [`vec`] and [`string`] with a length range, [`option`] and [`just`];
+//! - tuples of strategies, one element per test function parameter.
+//!
+//! Implement [`Arbitrary`] for your own types to use them without a `strategy = ...`.
+
+use crate::runtime::{panic_message, silence_panics};
+use std::fmt::Debug;
+use std::ops::{Range, RangeInclusive};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Environment variable that replaces the seed of every generated test, to replay a failure.
+pub const SEED_ENV_VAR: &str = "RUST_TEST_SEED";
+
+/// Number of cases run by a generated test when `cases = ...` is not given.
+pub const DEFAULT_CASES: u32 = 256;
+
+/// Upper bound of failing inputs tried while shrinking a counterexample.
+const MAX_SHRINK_STEPS: usize = 1024;
+
+/// Longest `Vec` or `String` produced by [`any`].
+const DEFAULT_MAX_LEN: usize = 16;
+
+/// A small deterministic random number generator (SplitMix64).
+///
+/// The same seed always produces the same sequence, on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed value in `low..=high`.
+    pub fn between(&mut self, low: i128, high: i128) -> i128 {
+        debug_assert!(low <= high);
+        let span = (high - low) as u128 + 1;
+        low + (self.next_u64() as u128 % span) as i128
+    }
+
+    /// `true` with a probability of one in `n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.next_u64() % n == 0
+    }
+
+    /// A uniformly distributed value in `0.0..1.0`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Produces random values and simpler variants of them.
+pub trait Strategy {
+    type Value: Clone + Debug;
+
+    /// Produces a random value.
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Returns values simpler than `value`, the simplest first.
+    ///
+    /// Every returned value must also be one that `generate` could produce.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+/// Types with a default [`Strategy`], used by [`any`] and by `Generated` without a `strategy`.
+pub trait Arbitrary: Clone + Debug {
+    type Strategy: Strategy<Value = Self>;
+
+    fn arbitrary() -> Self::Strategy;
+}
+
+/// The default strategy of `T`.
+pub fn any<T: Arbitrary>() -> T::Strategy {
+    T::arbitrary()
+}
+
+/// Shrink candidates of an integer, moving towards `target`: the target itself,
+/// then values halving the distance, then the neighbour closest to the target.
+fn shrink_towards(value: i128, target: i128) -> Vec<i128> {
+    let mut candidates = Vec::new();
+    let mut distance = value - target;
+    while distance != 0 {
+        let candidate = value - distance;
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+        distance /= 2;
+    }
+    candidates
+}
+
+macro_rules! int_strategies {
+    ($($t:ty),*) => {$(
+        impl Strategy for RangeInclusive<$t> {
+            type Value = $t;
+
+            fn generate(&self, rng: &mut Rng) -> $t {
+                let (low, high) = (*self.start() as i128, *self.end() as i128);
+                // Boundaries find most bugs, so pick them more often than chance would
+                if rng.one_in(8) {
+                    let edges = [low, high, 0i128.clamp(low, high)];
+                    return edges[rng.between(0, 2) as usize] as $t;
+                }
+                rng.between(low, high) as $t
+            }
+
+            fn shrink(&self, value: &$t) -> Vec<$t> {
+                let (low, high) = (*self.start() as i128, *self.end() as i128);
+                shrink_towards(*value as i128, 0i128.clamp(low, high))
+                    .into_iter()
+                    .map(|v| v as $t)
+                    .collect()
+            }
+        }
+
+        impl Strategy for Range<$t> {
+            type Value = $t;
+
+            fn generate(&self, rng: &mut Rng) -> $t {
+                assert!(self.start < self.end, "Cannot generate values from an empty range {:?}", self);
+                (self.start..=self.end - 1).generate(rng)
+            }
+
+            fn shrink(&self, value: &$t) -> Vec<$t> {
+                (self.start..=self.end - 1).shrink(value)
+            }
+        }
+
+        impl Arbitrary for $t {
+            type Strategy = RangeInclusive<$t>;
+
+            fn arbitrary() -> Self::Strategy {
+                <$t>::MIN..=<$t>::MAX
+            }
+        }
+    )*};
+}
+
+int_strategies!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! float_strategies {
+    ($($t:ty),*) => {$(
+        impl Strategy for RangeInclusive<$t> {
+            type Value = $t;
+
+            fn generate(&self, rng: &mut Rng) -> $t {
+                let (low, high) = (*self.start(), *self.end());
+                if rng.one_in(8) {
+                    let edges = [low, high, (0.0 as $t).clamp(low, high)];
+                    return edges[rng.between(0, 2) as usize];
+                }
+                (low as f64 + rng.next_f64() * (high as f64 - low as f64)).clamp(low as f64, high as f64) as $t
+            }
+
+            fn shrink(&self, value: &$t) -> Vec<$t> {
+                let (low, high) = (*self.start(), *self.end());
+                let mut candidates = Vec::new();
+                for candidate in [(0.0 as $t).clamp(low, high), value.trunc(), value / 2.0] {
+                    if candidate != *value
+                        && candidate.is_finite()
+                        && (low..=high).contains(&candidate)
+                        && !candidates.contains(&candidate)
+                    {
+                        candidates.push(candidate);
+                    }
+                }
+                candidates
+            }
+        }
+
+        impl Strategy for Range<$t> {
+            type Value = $t;
+
+            fn generate(&self, rng: &mut Rng) -> $t {
+                assert!(self.start < self.end, "Cannot generate values from an empty range {:?}", self);
+                let value = (self.start..=self.end).generate(rng);
+                if value < self.end { value } else { self.start }
+            }
+
+            fn shrink(&self, value: &$t) -> Vec<$t> {
+                (self.start..=self.end).shrink(value).into_iter().filter(|v| *v < self.end).collect()
+            }
+        }
+
+        impl Arbitrary for $t {
+            type Strategy = RangeInclusive<$t>;
+
+            /// Finite values of a magnitude up to a million.
+            fn arbitrary() -> Self::Strategy {
+                -1_000_000.0..=1_000_000.0
+            }
+        }
+    )*};
+}
+
+float_strategies!(f32, f64);
+
+impl Strategy for RangeInclusive<char> {
+    type Value = char;
+
+    fn generate(&self, rng: &mut Rng) -> char {
+        let (low, high) = (*self.start() as i128, *self.end() as i128);
+        // Surrogates aren't characters, so retry until the code point is valid
+        loop {
+            if let Some(c) = char::from_u32(rng.between(low, high) as u32) {
+                return c;
+            }
+        }
+    }
+
+    fn shrink(&self, value: &char) -> Vec<char> {
+        let target = ('a' as i128).clamp(*self.start() as i128, *self.end() as i128);
+        shrink_towards(*value as i128, target)
+            .into_iter()
+            .filter_map(|c| char::from_u32(c as u32))
+            .collect()
+    }
+}
+
+/// Strategy of `bool`, shrinking `true` to `false`.
+#[derive(Clone, Debug)]
+pub struct BoolStrategy;
+
+impl Strategy for BoolStrategy {
+    type Value = bool;
+
+    fn generate(&self, rng: &mut Rng) -> bool {
+        rng.one_in(2)
+    }
+
+    fn shrink(&self, value: &bool) -> Vec<bool> {
+        if *value { vec![false] } else { Vec::new() }
+    }
+}
+
+impl Arbitrary for bool {
+    type Strategy = BoolStrategy;
+
+    fn arbitrary() -> Self::Strategy {
+        BoolStrategy
+    }
+}
+
+/// Strategy of `char`: mostly printable ASCII, sometimes any character.
+#[derive(Clone, Debug)]
+pub struct CharStrategy;
+
+impl Strategy for CharStrategy {
+    type Value = char;
+
+    fn generate(&self, rng: &mut Rng) -> char {
+        if rng.one_in(4) {
+            ('\0'..=char::MAX).generate(rng)
+        } else {
+            (' '..='~').generate(rng)
+        }
+    }
+
+    fn shrink(&self, value: &char) -> Vec<char> {
+        ('\0'..=char::MAX).shrink(value)
+    }
+}
+
+impl Arbitrary for char {
+    type Strategy = CharStrategy;
+
+    fn arbitrary() -> Self::Strategy {
+        CharStrategy
+    }
+}
+
+/// Strategy of `Vec`s of values of `element`, see [`vec`].
+#[derive(Clone, Debug)]
+pub struct VecStrategy<S> {
+    element: S,
+    len: RangeInclusive<usize>,
+}
+
+/// Vectors with a length in `len` and elements produced by `element`.
+pub fn vec<S: Strategy>(element: S, len: RangeInclusive<usize>) -> VecStrategy<S> {
+    VecStrategy { element, len }
+}
+
+impl<S: Strategy> Strategy for VecStrategy<S> {
+    type Value = Vec<S::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Vec<S::Value> {
+        let len = self.len.generate(rng);
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    fn shrink(&self, value: &Vec<S::Value>) -> Vec<Vec<S::Value>> {
+        let min_len = *self.len.start();
+        let mut candidates = Vec::new();
+        // Shorter vectors first: keep the shortest allowed prefix, then drop halves, then single elements
+        if value.len() > min_len {
+            candidates.push(value[..min_len].to_vec());
+            let half = value.len() / 2;
+            if half > 0 && value.len() - half >= min_len {
+                candidates.push(value[half..].to_vec());
+                candidates.push(value[..value.len() - half].to_vec());
+            }
+            for i in 0..value.len() {
+                let mut shorter = value.clone();
+                shorter.remove(i);
+                candidates.push(shorter);
+            }
+        }
+        for (i, element) in value.iter().enumerate() {
+            for simpler in self.element.shrink(element) {
+                let mut candidate = value.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    type Strategy = VecStrategy<T::Strategy>;
+
+    fn arbitrary() -> Self::Strategy {
+        vec(any::<T>(), 0..=DEFAULT_MAX_LEN)
+    }
+}
+
+/// Strategy of `String`s of characters produced by `chars`, see [`string`].
+#[derive(Clone, Debug)]
+pub struct StringStrategy<S> {
+    chars: VecStrategy<S>,
+}
+
+/// Strings with a length in `len` characters, each produced by `chars`,
+/// e.g. `string('a'..='z', 1..=8)`.
+pub fn string<S: Strategy<Value = char>>(chars: S, len: RangeInclusive<usize>) -> StringStrategy<S> {
+    StringStrategy { chars: vec(chars, len) }
+}
+
+impl<S: Strategy<Value = char>> Strategy for StringStrategy<S> {
+    type Value = String;
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        self.chars.generate(rng).into_iter().collect()
+    }
+
+    fn shrink(&self, value: &String) -> Vec<String> {
+        self.chars
+            .shrink(&value.chars().collect())
+            .into_iter()
+            .map(|chars| chars.into_iter().collect())
+            .collect()
+    }
+}
+
+impl Arbitrary for String {
+    type Strategy = StringStrategy<CharStrategy>;
+
+    fn arbitrary() -> Self::Strategy {
+        string(CharStrategy, 0..=DEFAULT_MAX_LEN)
+    }
+}
+
+/// Strategy of `Option`s of values of `inner`, see [`option`].
+#[derive(Clone, Debug)]
+pub struct OptionStrategy<S> {
+    inner: S,
+}
+
+/// `None` in about a quarter of the cases, otherwise `Some` value produced by `inner`.
+pub fn option<S: Strategy>(inner: S) -> OptionStrategy<S> {
+    OptionStrategy { inner }
+}
+
+impl<S: Strategy> Strategy for OptionStrategy<S> {
+    type Value = Option<S::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Option<S::Value> {
+        if rng.one_in(4) { None } else { Some(self.inner.generate(rng)) }
+    }
+
+    fn shrink(&self, value: &Option<S::Value>) -> Vec<Option<S::Value>> {
+        match value {
+            None => Vec::new(),
+            Some(inner) => std::iter::once(None)
+                .chain(self.inner.shrink(inner).into_iter().map(Some))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    type Strategy = OptionStrategy<T::Strategy>;
+
+    fn arbitrary() -> Self::Strategy {
+        option(any::<T>())
+    }
+}
+
+/// Strategy that always produces the same value, see [`just`].
+#[derive(Clone, Debug)]
+pub struct Just<T>(pub T);
+
+/// Always `value`, e.g. to keep one parameter fixed.
+pub fn just<T: Clone + Debug>(value: T) -> Just<T> {
+    Just(value)
+}
+
+impl<T: Clone + Debug> Strategy for Just<T> {
+    type Value = T;
+
+    fn generate(&self, _rng: &mut Rng) -> T {
+        self.0.clone()
+    }
+
+    fn shrink(&self, _value: &T) -> Vec<T> {
+        Vec::new()
+    }
+}
+
+macro_rules! tuple_strategies {
+    ($(($($s:ident $i:tt),+)),*) => {$(
+        impl<$($s: Strategy),+> Strategy for ($($s,)+) {
+            type Value = ($($s::Value,)+);
+
+            fn generate(&self, rng: &mut Rng) -> Self::Value {
+                ($(self.$i.generate(rng),)+)
+            }
+
+            /// Shrinks one element at a time, keeping the others.
+            fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                let mut candidates = Vec::new();
+                $(
+                    for simpler in self.$i.shrink(&value.$i) {
+                        let mut candidate = value.clone();
+                        candidate.$i = simpler;
+                        candidates.push(candidate);
+                    }
+                )+
+                candidates
+            }
+        }
+
+        impl<$($s: Arbitrary),+> Arbitrary for ($($s,)+) {
+            type Strategy = ($($s::Strategy,)+);
+
+            fn arbitrary() -> Self::Strategy {
+                ($(any::<$s>(),)+)
+            }
+        }
+    )*};
+}
+
+tuple_strategies!(
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+);
+
+/// A failing input reduced by [`shrink`].
+#[derive(Debug)]
+pub struct Counterexample<T> {
+    pub input: T,
+    /// Panic message of the test run with `input`.
+    pub message: String,
+    /// Number of simpler failing inputs found on the way to `input`.
+    pub steps: usize,
+}
+
+/// Repeatedly replaces `input` with its first simpler variant for which `fails` returns an error,
+/// until no variant fails or the step limit is reached.
+pub fn shrink<S: Strategy>(
+    strategy: &S,
+    input: S::Value,
+    message: String,
+    fails: impl Fn(&S::Value) -> Option<String>,
+) -> Counterexample<S::Value> {
+    let mut current = Counterexample { input, message, steps: 0 };
+    let mut tried = 0;
+    'shrinking: while tried < MAX_SHRINK_STEPS {
+        for candidate in strategy.shrink(&current.input) {
+            tried += 1;
+            if let Some(message) = fails(&candidate) {
+                current = Counterexample { input: candidate, message, steps: current.steps + 1 };
+                continue 'shrinking;
+            }
+            if tried >= MAX_SHRINK_STEPS {
+                break;
+            }
+        }
+        break;
+    }
+    current
+}
+
+/// The seed from [`SEED_ENV_VAR`], else `seed`, else one derived from the clock.
+fn choose_seed(seed: Option<u64>) -> u64 {
+    if let Ok(value) = std::env::var(SEED_ENV_VAR) {
+        return value
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("{} must be an unsigned integer, but got `{}`", SEED_ENV_VAR, value));
+    }
+    seed.unwrap_or_else(|| {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Rng::new(now.as_nanos() as u64).next_u64()
+    })
+}
+
+/// Runs `test` with `cases` inputs produced by `strategy`.
+///
+/// # Panics
+///
+/// Panics on the first failing input, after shrinking it, with the original and the minimal
+/// failing input and the seed to replay the run with. The panics of the inputs tried while
+/// shrinking are not printed.
+pub fn run<S: Strategy>(strategy: S, cases: u32, seed: Option<u64>, test: impl Fn(S::Value)) {
+    let seed = choose_seed(seed);
+    let mut rng = Rng::new(seed);
+    let fails = |input: &S::Value| {
+        catch_unwind(AssertUnwindSafe(|| test(input.clone())))
+            .err()
+            .map(|payload| panic_message(&*payload))
+    };
+
+    for passed in 0..cases {
+        let input = strategy.generate(&mut rng);
+        if let Some(message) = fails(&input) {
+            let original = format!("{:?}", input);
+            // The simpler inputs are expected to fail too, only the minimal one is reported
+            let minimal = silence_panics(|| shrink(&strategy, input, message, fails));
+            panic!(
+                "Generated test case failed after {} passed case(s): {}\n  minimal input: {:?} (shrunk {} time(s))\n  original input: {}\n  seed: {} (set {}={} to replay)",
+                passed, minimal.message, minimal.input, minimal.steps, original, seed, SEED_ENV_VAR, seed
+            );
+        }
+    }
+}
@@ -2,7 +2,15 @@ pub mod attributes;
 
 mod doc_references;
 
-pub use attributes::{test_params as test_params, test_params_source as test_params_source};
+pub mod generated;
+
+#[doc(hidden)]
+pub mod runtime;
+
+pub use attributes::{
+    test_case as test_case, test_matrix as test_matrix, test_params as test_params,
+    test_params_source as test_params_source,
+};
 pub use doc_references::source_type::SourceType;
 
 #[cfg(test)]
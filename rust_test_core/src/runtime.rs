@@ -0,0 +1,556 @@
+//! Support code called by generated tests.
+//!
+//! Sources loaded with `#[test_params_source(..., load = runtime)]` are read by the same code
+//! the macro uses at compile time, so validation and conversion behave identically in both modes.
+
+use crate::attributes::test_params_source::{load_source, name_cases, SourceType};
+use crate::attributes::common::{serialize_json, value_to_suffix};
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::process::{ExitCode, Termination};
+use std::sync::{Arc, Mutex, Once};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use syn::ItemFn;
+
+/// A test case loaded when the test runs.
+#[derive(Debug)]
+pub struct Case {
+    pub name: String,
+    /// JSON of the test function arguments.
+    pub input: String,
+    /// JSON of the value the test function is expected to return.
+    pub expected: Option<String>,
+    /// Reason to skip the case, empty if none was given.
+    pub ignore: Option<String>,
+    /// Message the case is expected to panic with, empty to accept any panic.
+    pub should_panic: Option<String>,
+    /// Line of the CSV row the case was read from, if it is deserialized with [`from_csv_row`].
+    pub csv_row: Option<usize>,
+}
+
+/// Loads the test cases of `source` for a test function with the given signature.
+///
+/// # Panics
+///
+/// Panics if the source can't be read or doesn't match the signature.
+pub fn load_cases(
+    source: &str,
+    signature: &str,
+    manifest_dir: &str,
+    name_field: Option<&str>,
+) -> Vec<Case> {
+    let source: SourceType = syn::parse_str(source)
+        .unwrap_or_else(|e| panic!("Invalid data source `{}`: {}", source, e));
+    let input_fn: ItemFn = syn::parse_str(signature)
+        .unwrap_or_else(|e| panic!("Invalid test signature `{}`: {}", signature, e));
+
+    let mut loaded = load_source(&source, &input_fn, Some(Path::new(manifest_dir)))
+        .unwrap_or_else(|e| panic!("Could not load test data: {}", e));
+    if let Some(field) = name_field {
+        name_cases(&mut loaded.cases, field).unwrap_or_else(|e| panic!("Could not name test cases: {}", e));
+    }
+
+    loaded
+        .cases
+        .into_iter()
+        .enumerate()
+        .map(|(i, case)| {
+            let name = case.suffix.unwrap_or_else(|| value_to_suffix(&case.value));
+            let name = if name.is_empty() { i.to_string() } else { name };
+            let to_json = |value| {
+                serialize_json(value).unwrap_or_else(|e| panic!("Failed to serialize test case {}: {}", name, e))
+            };
+            Case {
+                input: to_json(&case.value),
+                expected: case.expected.as_ref().map(to_json),
+                ignore: case.ignore,
+                should_panic: case.should_panic,
+                csv_row: case.csv_row,
+                name,
+            }
+        })
+        .collect()
+}
+
+/// Runs `run` for every case that isn't ignored, then panics with a report of all failed cases, if any.
+/// A case marked with `should_panic` fails if it doesn't panic with the expected message.
+pub fn run_cases(cases: Vec<Case>, run: impl Fn(&Case)) {
+    let mut failures = Vec::new();
+    for case in &cases {
+        if let Some(reason) = &case.ignore {
+            if reason.is_empty() {
+                println!("case {} ignored", case.name);
+            } else {
+                println!("case {} ignored: {}", case.name, reason);
+            }
+            continue;
+        }
+
+        let result = catch_unwind(AssertUnwindSafe(|| run(case)));
+        let failure = match (result, &case.should_panic) {
+            (Ok(()), None) => None,
+            (Ok(()), Some(_)) => Some("test case did not panic as expected".to_string()),
+            (Err(payload), None) => Some(panic_message(&*payload)),
+            (Err(payload), Some(expected)) => {
+                let message = panic_message(&*payload);
+                (!message.contains(expected.as_str())).then(|| {
+                    format!("panic did not contain expected string\n    panic message: {:?}\n    expected substring: {:?}", message, expected)
+                })
+            }
+        };
+        if let Some(failure) = failure {
+            failures.push(format!("  {}: {}\n    input: {}", case.name, failure, case.input));
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "{} of {} test cases failed:\n{}",
+            failures.len(),
+            cases.len(),
+            failures.join("\n")
+        );
+    }
+}
+
+/// Deserializes a CSV row read as a JSON object of its cells into the fields of `T`.
+///
+/// Each cell is converted by the type of its field, so an empty cell is `""` for a `String`
+/// and `None` for an `Option`, and integers of any size fit their field.
+///
+/// # Panics
+///
+/// Panics with the row and column of a cell that doesn't fit its field.
+pub fn from_csv_row<T: DeserializeOwned>(json: &str, row: Option<usize>) -> T {
+    let object: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(json).unwrap_or_else(|e| panic!("Invalid CSV row {}: {}", json, e));
+    let headers: csv::StringRecord = object.keys().collect();
+    let cells: csv::StringRecord = object
+        .values()
+        .map(|value| match value {
+            serde_json::Value::Null => String::new(),
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+        .collect();
+    let location = row.map_or_else(|| "CSV row".to_string(), |row| format!("CSV row {}", row));
+    cells.deserialize(Some(&headers)).unwrap_or_else(|e| match e.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => match err.field().and_then(|i| headers.get(i as usize)) {
+            Some(header) => panic!("{}, column `{}`: {}", location, header, err.kind()),
+            None => panic!("{}: {}", location, err.kind()),
+        },
+        _ => panic!("{}: {}", location, e),
+    })
+}
+
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+/// Runs the `#[before_all]` and `#[after_all]` functions of a `#[test_fixture]` module, which keeps it in a static.
+pub struct ModuleHooks {
+    state: Mutex<ModuleState>,
+}
+
+struct ModuleState {
+    /// Result of `before_all`, `None` until the first test of the module starts. It runs only once,
+    /// and every test of the module reports its failure.
+    before_all: Option<Result<(), String>>,
+    after_all: Option<fn()>,
+    after_all_ran: bool,
+    running: usize,
+    finished: usize,
+}
+
+impl ModuleHooks {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        ModuleHooks {
+            state: Mutex::new(ModuleState {
+                before_all: None,
+                after_all: None,
+                after_all_ran: false,
+                running: 0,
+                finished: 0,
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ModuleState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Called by every test of the module before anything else. The first call runs `before_all`, while
+    /// concurrent tests wait for it. `tests` is the number of tests of the module that run by default:
+    /// once as many have finished, the returned guard of the last one runs `after_all`.
+    #[track_caller]
+    pub fn enter(&'static self, before_all: Option<fn()>, after_all: Option<fn()>, tests: usize) -> ModuleGuard {
+        let mut state = self.lock();
+        let result = state
+            .before_all
+            .get_or_insert_with(|| match before_all {
+                Some(before_all) => catch_unwind(before_all).map_err(|payload| panic_message(&*payload)),
+                None => Ok(()),
+            })
+            .clone();
+        state.after_all = after_all;
+        state.running += 1;
+        drop(state);
+
+        // The guard also counts a test failing here, so that the last test still leaves the module
+        let guard = ModuleGuard { hooks: self, tests };
+        if let Err(message) = result {
+            panic!("before_all failed: {}", message);
+        }
+        guard
+    }
+
+    /// Runs `after_all` once the last of the `tests` has finished and no other test is running,
+    /// unless `before_all` failed. It runs only once, even if more tests start afterwards.
+    fn leave(&self, tests: usize) -> Result<(), String> {
+        let mut state = self.lock();
+        state.running -= 1;
+        state.finished += 1;
+        if state.finished < tests || state.running > 0 || state.after_all_ran {
+            return Ok(());
+        }
+        state.after_all_ran = true;
+        match (&state.before_all, state.after_all) {
+            (Some(Ok(())), Some(after_all)) => catch_unwind(after_all).map_err(|payload| panic_message(&*payload)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returned by [`ModuleHooks::enter`] to a test, which leaves the module when it's dropped at the end of the test.
+/// If the test was the last one and `after_all` panics, the test fails with `after_all failed: ...`.
+#[must_use]
+pub struct ModuleGuard {
+    hooks: &'static ModuleHooks,
+    tests: usize,
+}
+
+impl Drop for ModuleGuard {
+    fn drop(&mut self) {
+        if let Err(message) = self.hooks.leave(self.tests) {
+            // A test that is already failing can't panic again
+            if thread::panicking() {
+                eprintln!("after_all failed: {}", message);
+            } else {
+                panic!("after_all failed: {}", message);
+            }
+        }
+    }
+}
+
+/// Wakes a thread blocked in `block_on`.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs a future to completion on the current thread. Async tests without a `runtime` run on it,
+/// so the future can't depend on the services of a runtime such as tokio's timers or I/O.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// A panic caught in a phase of a test in a `#[test_fixture]`, with the location it was raised at.
+pub struct Panic {
+    payload: Box<dyn Any + Send>,
+    location: Option<String>,
+}
+
+impl Panic {
+    fn new(payload: Box<dyn Any + Send>) -> Self {
+        let location = PANIC_LOCATION.with(|location| location.borrow_mut().take());
+        Panic { payload, location }
+    }
+}
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// Whether the panics of the current thread are expected and not printed, see [`silence_panics`].
+    static SILENT_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs a panic hook recording where the panics of the current thread are raised, before calling
+/// the hook that was installed before, which prints them as usual unless they are silenced.
+fn record_panic_locations() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let location = info.location().map(|location| location.to_string());
+            PANIC_LOCATION.with(|last| *last.borrow_mut() = location);
+            if !SILENT_PANICS.get() {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Calls a function without printing the panics raised by the current thread meanwhile, e.g. the
+/// expected failures while shrinking a counterexample.
+pub(crate) fn silence_panics<T>(f: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SILENT_PANICS.set(self.0);
+        }
+    }
+
+    record_panic_locations();
+    let _restore = Restore(SILENT_PANICS.replace(true));
+    f()
+}
+
+/// Forgets the location of a panic raised before, e.g. one caught in an earlier phase, so that it isn't
+/// reported for a panic raised without going through the panic hook, such as `resume_unwind`.
+fn clear_panic_location() {
+    PANIC_LOCATION.with(|location| location.borrow_mut().take());
+}
+
+/// Calls a function and catches a panic, as `std::panic::catch_unwind` does, along with its location.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    record_panic_locations();
+    clear_panic_location();
+    catch_unwind(AssertUnwindSafe(f)).map_err(Panic::new)
+}
+
+/// Runs a future and catches a panic while it is polled, along with its location.
+pub async fn catch_panic_async<F: Future>(future: F) -> Result<F::Output, Panic> {
+    record_panic_locations();
+    let mut future = std::pin::pin!(future);
+    std::future::poll_fn(move |cx| {
+        // Cleared on every poll, as the future may be polled on another thread each time
+        clear_panic_location();
+        match catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            // Read on the thread that polled the future, where the panic was raised
+            Err(payload) => Poll::Ready(Err(Panic::new(payload))),
+        }
+    })
+    .await
+}
+
+/// Collects the panics of the phases of a test in a `#[test_fixture]`: its setup functions, the test
+/// itself and its teardown functions, which run even if a phase before them failed.
+#[derive(Default)]
+pub struct Phases {
+    failures: Vec<(&'static str, Option<&'static str>, Panic)>,
+}
+
+impl Phases {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a phase failed so far, in which case the remaining setup functions and the test are skipped.
+    pub fn failed(&self) -> bool {
+        !self.failures.is_empty()
+    }
+
+    /// Returns the result of a phase, or records its panic with the name of the phase and of the function.
+    pub fn check<T>(&mut self, phase: &'static str, function: Option<&'static str>, result: Result<T, Panic>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(panic) => {
+                self.failures.push((phase, function, panic));
+                None
+            }
+        }
+    }
+
+    /// Returns the value returned by the test if all phases succeeded. If only the test failed, its panic
+    /// is resumed as it is, e.g. for `should_panic`. So is the panic of a setup function failing alone,
+    /// after printing where it was raised. Otherwise the test fails with a report of all failures.
+    pub fn finish<T>(mut self, value: Option<T>) -> T {
+        match (value, self.failures.as_slice()) {
+            (Some(value), []) => return value,
+            (_, [("test", _, _)]) => {
+                let (_, _, panic) = self.failures.remove(0);
+                std::panic::resume_unwind(panic.payload);
+            }
+            (_, [("setup", _, _)]) => {
+                let (phase, function, panic) = self.failures.remove(0);
+                eprintln!("{} failed\n  {}", phase, Self::location(function, &panic));
+                std::panic::resume_unwind(panic.payload);
+            }
+            _ => {}
+        }
+
+        let report = self
+            .failures
+            .iter()
+            .map(|(phase, function, panic)| {
+                format!("{} failed: {}\n  {}", phase, panic_message(&*panic.payload), Self::location(*function, panic))
+            })
+            .collect::<Vec<_>>();
+        panic!("{}", report.join("\n"));
+    }
+
+    /// Where a panic was raised, and in which function if it isn't the test itself.
+    fn location(function: Option<&str>, panic: &Panic) -> String {
+        let location = panic.location.as_deref().unwrap_or("unknown location");
+        match function {
+            Some(function) => format!("in `{}` at {}", function, location),
+            None => format!("at {}", location),
+        }
+    }
+}
+
+/// A value returned by a test function, checked with `assert_success` or `assert_expected_json`.
+///
+/// Generated tests call these methods on a reference to a reference to it: method lookup takes the
+/// first implementation for the most referenced receiver whose bounds the value meets, so more specific
+/// checks are implemented for more referenced types.
+pub struct Returned<T>(Cell<Option<T>>);
+
+impl<T> Returned<T> {
+    pub fn new(value: T) -> Self {
+        Returned(Cell::new(Some(value)))
+    }
+
+    fn take(&self) -> T {
+        self.0.take().expect("The returned value was already checked")
+    }
+}
+
+/// Fails a test case whose test function returned an `Err`, whether or not the `Result` implements `Termination`.
+pub trait CheckResult {
+    fn assert_success(&self, input: &str);
+}
+
+impl<T, E: Debug> CheckResult for &&Returned<Result<T, E>> {
+    #[track_caller]
+    fn assert_success(&self, input: &str) {
+        if let Err(e) = self.take() {
+            panic!("Test case returned Err({:?}) for input: {}", e, input);
+        }
+    }
+}
+
+/// Fails a test case whose test function returned another `Termination` value reporting a failure.
+pub trait CheckTermination {
+    fn assert_success(&self, input: &str);
+}
+
+impl<T: Termination> CheckTermination for &Returned<T> {
+    #[track_caller]
+    fn assert_success(&self, input: &str) {
+        if self.take().report() != ExitCode::SUCCESS {
+            panic!("Test case returned a failure for input: {}", input);
+        }
+    }
+}
+
+/// Ignores any other returned value.
+pub trait IgnoreReturned {
+    fn assert_success(&self, input: &str);
+}
+
+impl<T> IgnoreReturned for Returned<T> {
+    fn assert_success(&self, _input: &str) {}
+}
+
+/// Compares the returned value with the expected value of a test case loaded at run time.
+pub trait CompareExpected {
+    fn assert_expected_json(&self, expected: &str);
+}
+
+impl<T: DeserializeOwned + PartialEq + Debug> CompareExpected for &Returned<T> {
+    #[track_caller]
+    fn assert_expected_json(&self, expected: &str) {
+        let expected: T = serde_json::from_str(expected)
+            .unwrap_or_else(|e| panic!("Invalid expected value {}: {}", expected, e));
+        assert_expected(&self.take(), &expected);
+    }
+}
+
+/// Rejects an expected value for a return type that can't be compared. Whether a test case loaded
+/// at run time has an expected value is only known when it runs, so this can't be a compile error.
+pub trait CannotCompareExpected {
+    fn assert_expected_json(&self, expected: &str);
+}
+
+impl<T> CannotCompareExpected for Returned<T> {
+    #[track_caller]
+    fn assert_expected_json(&self, expected: &str) {
+        panic!(
+            "Can't compare the returned value with the expected value {}: the return type must implement Deserialize, PartialEq and Debug",
+            expected
+        );
+    }
+}
+
+/// Compares the value returned by a test function with the expected one.
+///
+/// # Panics
+///
+/// Panics with a line diff of both values, pretty-printed with `{:#?}`, if they differ.
+pub fn assert_expected<T: PartialEq + Debug>(actual: &T, expected: &T) {
+    if actual != expected {
+        panic!(
+            "Returned value does not match the expected value\n(- expected, + actual)\n{}",
+            line_diff(&format!("{:#?}", expected), &format!("{:#?}", actual))
+        );
+    }
+}
+
+/// Diffs two texts line by line, marking removed lines with `-` and added lines with `+`.
+pub(crate) fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
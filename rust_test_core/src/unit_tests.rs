@@ -1,2 +1,6 @@
 mod test_value_to_suffix;
-mod test_generate_test_set;
\ No newline at end of file
+mod test_generate_test_set;
+mod test_line_diff;
+mod test_covering_array;mod test_generated;
+
+mod test_module_hooks;
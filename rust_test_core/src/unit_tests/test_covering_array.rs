@@ -0,0 +1,71 @@
+use crate::attributes::test_matrix::covering_array::covering_array;
+
+/// Checks that every combination of values of any `strength` parameters appears in a row.
+fn assert_covers(sizes: &[usize], strength: usize, rows: &[Vec<usize>]) {
+    fn check(sizes: &[usize], strength: usize, rows: &[Vec<usize>], params: &mut Vec<usize>, start: usize) {
+        if params.len() == strength {
+            let total: usize = params.iter().map(|&p| sizes[p]).product();
+            let mut seen = std::collections::BTreeSet::new();
+            for row in rows {
+                seen.insert(params.iter().map(|&p| row[p]).collect::<Vec<_>>());
+            }
+            assert_eq!(seen.len(), total, "parameters {:?} are not fully covered", params);
+            return;
+        }
+        for p in start..sizes.len() {
+            params.push(p);
+            check(sizes, strength, rows, params, p + 1);
+            params.pop();
+        }
+    }
+    for row in rows {
+        assert_eq!(row.len(), sizes.len());
+        assert!(row.iter().zip(sizes).all(|(&v, &size)| v < size));
+    }
+    check(sizes, strength, rows, &mut Vec::new(), 0);
+}
+
+#[test]
+fn test_pairwise_covers_all_pairs() {
+    let sizes = [5; 6];
+    let rows = covering_array(&sizes, 2);
+    assert_covers(&sizes, 2, &rows);
+    // The full cross product would be 15625 rows, the optimum is 25
+    assert!(rows.len() <= 45, "{} rows", rows.len());
+}
+
+#[test]
+fn test_three_wise_covers_all_triples() {
+    let sizes = [3, 2, 4, 3, 2, 3];
+    let rows = covering_array(&sizes, 3);
+    assert_covers(&sizes, 3, &rows);
+    assert!(rows.len() < sizes.iter().product::<usize>());
+}
+
+#[test]
+fn test_covering_array_is_deterministic() {
+    let sizes = [4, 3, 5, 2, 3];
+    assert_eq!(covering_array(&sizes, 2), covering_array(&sizes, 2));
+}
+
+#[test]
+fn test_covering_array_without_duplicates() {
+    let sizes = [2, 3, 2, 2];
+    let rows = covering_array(&sizes, 2);
+    let unique: std::collections::BTreeSet<_> = rows.iter().collect();
+    assert_eq!(unique.len(), rows.len());
+}
+
+#[test]
+fn test_strength_above_parameter_count_is_full_product() {
+    let rows = covering_array(&[2, 3], 3);
+    assert_eq!(rows, vec![vec![0, 0], vec![0, 1], vec![0, 2], vec![1, 0], vec![1, 1], vec![1, 2]]);
+}
+
+#[test]
+fn test_strength_one_uses_each_value() {
+    let sizes = [3, 4, 2];
+    let rows = covering_array(&sizes, 1);
+    assert_covers(&sizes, 1, &rows);
+    assert_eq!(rows.len(), 4);
+}
@@ -15,6 +15,13 @@ fn test_generate_test_set_empty_suffix() {
         value: Value::Null,
         span: Span::call_site(),
         suffix: None,
+        expected: None,
+        ignore: None,
+        should_panic: None,
+        raw: None,
+        constants: Vec::new(),
+        expected_constant: None,
+        csv_row: None,
     }];
     let fn_name = format_ident!("my_test");
     let type_name: syn::Type = parse_quote! { u32 };
@@ -44,6 +51,13 @@ fn test_generate_test_set_serialization_error() {
             value: Value::Null,
             span: Span::call_site(),
             suffix: None,
+            expected: None,
+            ignore: None,
+            should_panic: None,
+            raw: None,
+            constants: Vec::new(),
+            expected_constant: None,
+            csv_row: None,
         }],
         fn_name.clone(),
         Some(type_name.clone()),
@@ -64,11 +78,25 @@ fn test_generate_test_set_serialization_error() {
                 value: Value::Null,
                 span: Span::call_site(),
                 suffix: None,
+                expected: None,
+                ignore: None,
+                should_panic: None,
+                raw: None,
+                constants: Vec::new(),
+                expected_constant: None,
+                csv_row: None,
             },
             ValueWithSpan {
                 value: Value::Null,
                 span: Span::call_site(),
                 suffix: None,
+                expected: None,
+                ignore: None,
+                should_panic: None,
+                raw: None,
+                constants: Vec::new(),
+                expected_constant: None,
+                csv_row: None,
             },
         ],
         fn_name,
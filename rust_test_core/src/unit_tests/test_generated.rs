@@ -0,0 +1,45 @@
+use crate::generated::{any, shrink, string, vec, Rng, Strategy};
+
+#[test]
+fn test_rng_is_deterministic() {
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+    let first: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+    let second: Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+    assert_eq!(first, second);
+    assert_ne!(Rng::new(43).next_u64(), first[0]);
+}
+
+#[test]
+fn test_generated_values_stay_in_range() {
+    let mut rng = Rng::new(1);
+    for _ in 0..1000 {
+        assert!((-5..=5i8).contains(&(-5..=5i8).generate(&mut rng)));
+        assert!((10..20u64).contains(&(10..20u64).generate(&mut rng)));
+        assert!(('a'..='f').contains(&('a'..='f').generate(&mut rng)));
+        let value = (0.5..2.0f64).generate(&mut rng);
+        assert!((0.5..2.0).contains(&value));
+        let text = string('x'..='z', 1..=3).generate(&mut rng);
+        assert!((1..=3).contains(&text.chars().count()));
+    }
+}
+
+#[test]
+fn test_shrink_candidates() {
+    assert_eq!((0..=100u32).shrink(&100), vec![0, 50, 75, 88, 94, 97, 99]);
+    assert_eq!((-100..=-10i32).shrink(&-12), vec![-10, -11]);
+    assert_eq!((0..=10u32).shrink(&0), Vec::<u32>::new());
+    assert_eq!(any::<bool>().shrink(&true), vec![false]);
+    assert_eq!(any::<Option<u8>>().shrink(&Some(1)), vec![None, Some(0)]);
+    assert_eq!(vec(0..=9u8, 1..=5).shrink(&vec![3]), vec![vec![0], vec![2]]);
+}
+
+#[test]
+fn test_shrink_to_minimal_counterexample() {
+    let strategy = (0..=1000i32, vec(any::<u8>(), 0..=10));
+    let fails = |(n, v): &(i32, Vec<u8>)| (*n >= 17 && v.len() >= 2).then(|| "failed".to_string());
+    let minimal = shrink(&strategy, (900, vec![7, 8, 9, 10]), "failed".to_string(), fails);
+    assert_eq!(minimal.input, (17, vec![0, 0]));
+    assert_eq!(minimal.message, "failed");
+    assert!(minimal.steps > 0);
+}
@@ -0,0 +1,23 @@
+use crate::runtime::line_diff;
+
+#[test]
+fn test_line_diff_equal() {
+    assert_eq!(line_diff("a\nb", "a\nb"), "  a\n  b");
+}
+
+#[test]
+fn test_line_diff_changed_line() {
+    let expected = "User {\n    name: \"Alice\",\n    age: 30,\n}";
+    let actual = "User {\n    name: \"Alice\",\n    age: 31,\n}";
+    assert_eq!(
+        line_diff(expected, actual),
+        "  User {\n      name: \"Alice\",\n-     age: 30,\n+     age: 31,\n  }"
+    );
+}
+
+#[test]
+fn test_line_diff_added_and_removed_lines() {
+    assert_eq!(line_diff("a\nb\nc", "b\nc\nd"), "- a\n  b\n  c\n+ d");
+    assert_eq!(line_diff("", "a"), "+ a");
+    assert_eq!(line_diff("a", ""), "- a");
+}
@@ -0,0 +1,65 @@
+use crate::runtime::ModuleHooks;
+use std::panic::catch_unwind;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static BEFORE_ALL: AtomicU32 = AtomicU32::new(0);
+static AFTER_ALL: AtomicU32 = AtomicU32::new(0);
+
+fn before_all() {
+    BEFORE_ALL.fetch_add(1, Ordering::SeqCst);
+}
+
+fn after_all() {
+    AFTER_ALL.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn test_after_all_runs_after_the_last_test() {
+    static HOOKS: ModuleHooks = ModuleHooks::new();
+    let first = HOOKS.enter(Some(before_all), Some(after_all), 2);
+    let second = HOOKS.enter(Some(before_all), Some(after_all), 2);
+    assert_eq!(BEFORE_ALL.load(Ordering::SeqCst), 1);
+    drop(first);
+    assert_eq!(AFTER_ALL.load(Ordering::SeqCst), 0);
+    drop(second);
+    assert_eq!(AFTER_ALL.load(Ordering::SeqCst), 1);
+
+    // A test running after them, e.g. an ignored one, runs neither of them again
+    drop(HOOKS.enter(Some(before_all), Some(after_all), 2));
+    assert_eq!(BEFORE_ALL.load(Ordering::SeqCst), 1);
+    assert_eq!(AFTER_ALL.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_after_all_failure_fails_the_last_test() {
+    static HOOKS: ModuleHooks = ModuleHooks::new();
+    let guard = HOOKS.enter(None, Some(|| panic!("cleanup failed")), 1);
+    let payload = catch_unwind(move || drop(guard)).unwrap_err();
+    assert_eq!(payload.downcast_ref::<String>().unwrap(), "after_all failed: cleanup failed");
+}
+
+#[test]
+fn test_before_all_failure_fails_every_test() {
+    static HOOKS: ModuleHooks = ModuleHooks::new();
+    static STARTED: AtomicU32 = AtomicU32::new(0);
+    static CLEANED_UP: AtomicU32 = AtomicU32::new(0);
+    let enter = || {
+        drop(HOOKS.enter(
+            Some(|| {
+                STARTED.fetch_add(1, Ordering::SeqCst);
+                panic!("no database");
+            }),
+            Some(|| {
+                CLEANED_UP.fetch_add(1, Ordering::SeqCst);
+            }),
+            2,
+        ));
+    };
+    for _ in 0..2 {
+        let payload = catch_unwind(enter).unwrap_err();
+        assert_eq!(payload.downcast_ref::<String>().unwrap(), "before_all failed: no database");
+    }
+    assert_eq!(STARTED.load(Ordering::SeqCst), 1);
+    // Nothing was set up to clean up
+    assert_eq!(CLEANED_UP.load(Ordering::SeqCst), 0);
+}
@@ -17,4 +17,23 @@ fn test_value_to_suffix_all_variants() {
     let mut map = serde_json::Map::new();
     map.insert("k".to_string(), Value::from(3));
     assert_eq!(value_to_suffix(&Value::Object(map)), "3");
-}
\ No newline at end of file
+}
+#[test]
+fn test_value_to_suffix_big_integers() {
+    use crate::attributes::common::{int_to_value, serialize_json};
+
+    let big = int_to_value(-170141183460469231731687303715884105728);
+    assert_eq!(value_to_suffix(&big), "_170141183460469231731687303715884105728");
+    assert_eq!(
+        serialize_json(&Value::Array(vec![big, int_to_value(u64::MAX as i128), Value::from("x")])).unwrap(),
+        r#"[-170141183460469231731687303715884105728,18446744073709551615,"x"]"#
+    );
+}
+
+#[test]
+fn test_serialize_json_keeps_strings() {
+    use crate::attributes::common::serialize_json;
+
+    let text = Value::String("\u{0}int:123".to_string());
+    assert_eq!(serialize_json(&text).unwrap(), r#""\u0000int:123""#);
+}
@@ -1,7 +1,9 @@
 pub use rust_test_proc_macro::{
-    setup, teardown, test_fixture, test_params, test_params_source, rust_test_seen_value,
+    after_all, before_all, setup, teardown, test_case, test_fixture, test_matrix, test_params, test_params_source,
+    rust_test_seen_value, rust_test_seen_expr, rust_test_seen_name, rust_test_runtime, rust_test_enter,
 };
 pub use rust_test_core::SourceType;
+pub use rust_test_core::generated;
 
 /// Returns the version of the framework.
 pub fn version() -> &'static str {
@@ -64,4 +66,5 @@ macro_rules! wait_for {
 #[doc(hidden)]
 pub mod __private {
     pub use serde_json;
+    pub use rust_test_core::runtime;
 }
\ No newline at end of file
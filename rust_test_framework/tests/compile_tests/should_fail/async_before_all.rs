@@ -0,0 +1,12 @@
+use rust_test_framework::test_fixture;
+
+#[test_fixture]
+mod tests {
+    #[rust_test_framework::before_all]
+    async fn start_server() {}
+
+    #[test]
+    fn test_dummy() {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use rust_test_framework::test_fixture;
+
+#[test_fixture]
+mod tests {
+    #[rust_test_framework::setup(order = "first")]
+    fn my_setup() {}
+
+    #[test]
+    fn test_dummy() {}
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use rust_test_framework::test_fixture;
+
+#[test_fixture]
+mod tests {
+    #[rust_test_framework::setup]
+    fn http() -> u16 {
+        80
+    }
+
+    #[rust_test_framework::setup]
+    fn https() -> u16 {
+        443
+    }
+
+    #[rust_test_framework::teardown]
+    fn close(port: u16) {}
+
+    #[test]
+    fn test_dummy() {}
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use rust_test_framework::test_fixture;
+
+#[test_fixture]
+mod tests {
+    #[rust_test_framework::setup]
+    fn my_setup() {}
+
+    #[rust_test_framework::teardown]
+    fn my_teardown(value: u32) {}
+
+    #[test]
+    fn test_dummy() {}
+}
+
+fn main() {}
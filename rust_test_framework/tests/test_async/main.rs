@@ -0,0 +1,169 @@
+use rust_test_framework::{setup, teardown, test_case, test_fixture, test_matrix, test_params, test_params_source};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// A future that is pending once, so the executor has to wake it.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+async fn double(value: u32) -> u32 {
+    YieldOnce(false).await;
+    value * 2
+}
+
+#[test_params(1)]
+#[test_params(2)]
+async fn test_without_runtime(value: u32) {
+    assert_eq!(double(value).await, value * 2);
+}
+
+#[test_params(1, expect = 2)]
+#[test_params(5, expect = 10)]
+async fn test_expect(value: u32) -> u32 {
+    double(value).await
+}
+
+#[test_params(0, should_panic = "zero")]
+async fn test_should_panic(value: u32) {
+    YieldOnce(false).await;
+    assert!(value > 0, "zero");
+}
+
+#[test_params(10, runtime = "tokio::test")]
+#[test_params(20)]
+async fn test_tokio_runtime(millis: u64) {
+    // Panics without a tokio runtime
+    tokio::time::sleep(Duration::from_millis(millis)).await;
+}
+
+#[test_matrix([1, 2], [3, 4], runtime = "tokio::test")]
+async fn test_matrix_tokio(a: u64, b: u64) {
+    tokio::time::sleep(Duration::from_millis(a * b)).await;
+}
+
+#[test_case(Duration::from_millis(5), runtime = "tokio::test")]
+async fn test_case_tokio(delay: Duration) {
+    tokio::time::sleep(delay).await;
+}
+
+#[test_params_source(JsonString("[1, 2, 3]"), runtime = "tokio::test")]
+async fn test_source_tokio(millis: u64) {
+    tokio::time::sleep(Duration::from_millis(millis)).await;
+}
+
+#[test_params_source(JsonString("[1, 2, 3]"), load = runtime)]
+async fn test_source_at_run_time(value: u32) {
+    assert_eq!(double(value).await, value * 2);
+}
+
+#[test_fixture]
+mod fixture {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static SETUP_COUNT: AtomicU32 = AtomicU32::new(0);
+    static TEARDOWN_COUNT: AtomicU32 = AtomicU32::new(0);
+
+    #[setup]
+    async fn async_setup() {
+        YieldOnce(false).await;
+        SETUP_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[teardown]
+    async fn async_teardown() {
+        YieldOnce(false).await;
+        TEARDOWN_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test_params(1)]
+    #[test_params(2)]
+    async fn test_async_hooks(value: u32) {
+        assert!(value > 0);
+        assert!(SETUP_COUNT.load(Ordering::SeqCst) > 0);
+    }
+
+    #[test_params(5, runtime = "tokio::test")]
+    async fn test_async_hooks_with_tokio(millis: u64) {
+        tokio::time::sleep(Duration::from_millis(millis)).await;
+        assert!(SETUP_COUNT.load(Ordering::SeqCst) > 0);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "failed in the test")]
+    async fn test_teardown_after_panic() {
+        YieldOnce(false).await;
+        panic!("failed in the test");
+    }
+}
+
+#[test_fixture]
+mod sync_hooks {
+    use super::*;
+    use rust_test_framework::test_case;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static SETUP_COUNT: AtomicU32 = AtomicU32::new(0);
+
+    #[setup]
+    fn sync_setup() {
+        SETUP_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test_case(3)]
+    async fn test_sync_setup(value: u32) {
+        assert_eq!(double(value).await, 6);
+        assert!(SETUP_COUNT.load(Ordering::SeqCst) > 0);
+    }
+
+    #[test]
+    fn test_sync_test() {
+        assert!(SETUP_COUNT.load(Ordering::SeqCst) > 0);
+    }
+}
+
+#[test_fixture]
+mod async_context {
+    use super::*;
+
+    pub struct Connection(u32);
+
+    #[setup]
+    async fn connect() -> Connection {
+        YieldOnce(false).await;
+        Connection(7)
+    }
+
+    #[teardown]
+    async fn disconnect(connection: Connection) {
+        YieldOnce(false).await;
+        assert_eq!(connection.0, 8, "connection not used");
+    }
+
+    #[tokio::test]
+    async fn test_async_context(connection: &mut Connection) {
+        YieldOnce(false).await;
+        connection.0 += 1;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "teardown failed: assertion `left == right` failed: connection not used")]
+    async fn test_async_teardown_failure(connection: &Connection) {
+        assert_eq!(connection.0, 7);
+    }
+}
@@ -0,0 +1,86 @@
+use rust_test_framework::{setup, test_case, test_fixture, test_params};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A type that can't be deserialized: it holds an `Arc` and a closure.
+struct User {
+    name: Arc<str>,
+    greet: Box<dyn Fn(&str) -> String>,
+}
+
+fn make_user(name: &str) -> User {
+    User {
+        name: Arc::from(name),
+        greet: Box::new(|name| format!("Hello, {}!", name)),
+    }
+}
+
+#[test_case(Duration::from_secs(3), make_user("x"))]
+#[test_case(Duration::from_millis(10), make_user("y"))]
+fn test_expressions(timeout: Duration, user: User) {
+    assert!(timeout <= Duration::from_secs(3));
+    assert_eq!((user.greet)(&user.name), format!("Hello, {}!", user.name));
+}
+
+#[test_case(vec![1, 2, 3].into_iter().map(|x| x * 2).collect(), name = "doubled")]
+#[test_case(Vec::new(), name = "empty")]
+fn test_named(values: Vec<u32>) {
+    assert!(values.iter().all(|v| v % 2 == 0));
+}
+
+#[test_case(Duration::from_secs(90), expect = Duration::from_secs(60))]
+#[test_case(Duration::from_secs(30), expect = Duration::from_secs(30))]
+fn test_expect(timeout: Duration) -> Duration {
+    timeout.min(Duration::from_secs(60))
+}
+
+#[test_case(1, 0, should_panic = "divide by zero")]
+#[test_case(1, 1, ignore = "slow")]
+fn test_markers(a: i32, b: i32) {
+    let _ = a / b;
+}
+
+#[test_case(Duration::from_secs(5).as_secs())]
+#[test_params(5)]
+fn test_mixed_with_params(value: u64) {
+    let _ = value;
+}
+
+const LIMIT: u32 = 10;
+
+#[test_case(LIMIT)]
+#[test_case(LIMIT - 1)]
+fn test_constants(value: u32) {
+    assert!(value <= LIMIT);
+}
+
+#[test]
+fn test_case_names() {
+    // Fails to compile if the tests are missing
+    let _ = test_expressions__case_1;
+    let _ = test_expressions__case_2;
+    let _ = test_named__doubled;
+    let _ = test_named__empty;
+    let _ = test_constants__case_1;
+    let _ = test_constants__case_2;
+}
+
+#[test_fixture]
+mod fixture {
+    use super::*;
+    use rust_test_framework::test_case;
+
+    static SETUP_RUNS: AtomicU32 = AtomicU32::new(0);
+
+    #[setup]
+    fn before_each() {
+        SETUP_RUNS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test_case(Arc::new(5))]
+    fn test_case_in_fixture(value: Arc<u32>) {
+        assert!(SETUP_RUNS.load(Ordering::SeqCst) > 0);
+        assert_eq!(*value, 5);
+    }
+}
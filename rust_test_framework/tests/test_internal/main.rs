@@ -1,5 +1,5 @@
 use quote::quote;
-use rust_test_core::test_params_source;
+use rust_test_core::{test_case, test_matrix, test_params, test_params_source};
 use std::sync::Mutex;
 
 static ENV_MUTEX: Mutex<()> = Mutex::new(());
@@ -296,4 +296,851 @@ fn test_invalid_utf8_path() {
 
     assert!(result.is_err());
     assert!(result.unwrap_err().to_string().contains("Path contains invalid UTF-8"));
-}
\ No newline at end of file
+}
+#[test]
+fn test_invalid_yaml() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { YamlFile("tests/test_data/invalid.yaml") };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Could not parse YAML file")
+    );
+
+    let attr = quote! { YamlString("[1, 2") };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Could not parse YAML"));
+}
+
+#[test]
+fn test_yaml_multi_document() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { YamlFile("tests/test_data/test_multi_document.yaml") };
+    let item = quote! { fn my_test(v: User) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_ok());
+    let res_str = result.unwrap().to_string();
+    assert!(res_str.contains("fn my_test__30_alice"));
+    assert!(res_str.contains("fn my_test__25_bob"));
+    assert!(res_str.contains("fn my_test__40_carol"));
+    assert!(res_str.contains("include_str"));
+
+    // Each document is validated against the parameter
+    let attr = quote! { YamlString("name: Alice\n---\nage: 30") };
+    let item = quote! { fn my_test(v: User) { let _ = v.name; } };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("missing required field `name`"));
+}
+
+#[test]
+fn test_toml_file() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { TomlFile("tests/test_data/test_cases.toml") };
+    let item = quote! { fn my_test(v: Addition) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_ok());
+    let res_str = result.unwrap().to_string();
+    assert!(res_str.contains("fn my_test__1_2_3"));
+    assert!(res_str.contains("fn my_test__10__4_6"));
+    assert!(res_str.contains("fn my_test__0_0_0"));
+    assert!(res_str.contains("include_str"));
+
+    // Parse errors point at the line and column
+    let attr = quote! { TomlFile("tests/test_data/invalid.toml") };
+    let result = test_params_source(attr, quote! { fn my_test(v: Addition) {} });
+    assert!(result.is_err());
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Could not parse TOML file tests/test_data/invalid.toml"), "{}", err);
+    assert!(err.contains("at line 3 column"), "{}", err);
+}
+
+#[test]
+fn test_csv_file() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { CsvFile("tests/test_data/test_users.csv") };
+    let item = quote! { fn my_test(v: User) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_ok());
+    let res_str = result.unwrap().to_string();
+    // Columns are coerced per column: numbers, strings and empty as null
+    assert!(res_str.contains("fn my_test__30_alice_al_9_5"));
+    assert!(res_str.contains("fn my_test__25_bob_null_7"));
+    assert!(res_str.contains("include_str"));
+
+    // Values that don't fit the parameter type name the row and column
+    let attr = quote! { CsvFile("tests/test_data/test_type_mismatch.csv") };
+    let item = quote! { fn my_test(id: u32, label: String) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("CSV row 3, column `id`: Expected number for type u32"), "{}", err);
+
+    // Struct rows are checked against the field types when deserialized, naming the row and column
+    let attr = quote! { CsvFile("tests/test_data/test_type_mismatch.csv") };
+    let item = quote! { fn my_test(v: Labeled) {} };
+    let res_str = test_params_source(attr, item).unwrap().to_string();
+    assert!(res_str.contains("runtime :: from_csv_row (\"{\\\"id\\\":\\\"two\\\",\\\"label\\\":\\\"two\\\"}\" , Some (3usize))"), "{}", res_str);
+
+    #[derive(serde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Labeled {
+        id: u32,
+        label: String,
+    }
+    let result = std::panic::catch_unwind(|| {
+        rust_test_core::runtime::from_csv_row::<Labeled>(r#"{"id":"two","label":"two"}"#, Some(3))
+    });
+    let message = result.unwrap_err().downcast_ref::<String>().cloned().unwrap();
+    assert!(message.starts_with("CSV row 3, column `id`: "), "{}", message);
+
+    // Column count must match the parameter count
+    let attr = quote! { CsvFile("tests/test_data/test_users.csv") };
+    let item = quote! { fn my_test(name: String, age: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("CSV row 2: expected 2 column(s) for 2 parameter(s), but got 4"));
+
+    // Unknown reader option
+    let attr = quote! { CsvFile("tests/test_data/test_users.csv", separator = ';') };
+    let item = quote! { fn my_test(v: User) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Unknown CsvFile option: separator"));
+}
+
+#[test]
+fn test_json_lines() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { JsonLines("tests/test_data/test_requests.ndjson") };
+    let item = quote! { fn my_test(v: Request) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_ok());
+    let res_str = result.unwrap().to_string();
+    // Tests are named after the line number, comments and blank lines are skipped
+    assert!(res_str.contains("fn my_test__line_2"));
+    assert!(res_str.contains("fn my_test__line_3"));
+    assert!(res_str.contains("fn my_test__line_6"));
+    assert!(!res_str.contains("fn my_test__line_1 "));
+    assert!(!res_str.contains("fn my_test__line_4"));
+    assert!(res_str.contains("include_str"));
+
+    let attr = quote! { JsonLines("tests/test_data/invalid.ndjson") };
+    let item = quote! { fn my_test(v: Request) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Could not parse JSON Lines file tests/test_data/invalid.ndjson"), "{}", err);
+    assert!(err.contains("at line 3 column"), "{}", err);
+}
+
+#[test]
+fn test_json_glob() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { JsonGlob("tests/test_data/json_glob/**/*.json") };
+    let item = quote! { fn my_test(v: User) { let _ = v.age; } };
+    let result = test_params_source(attr, item);
+    assert!(result.is_ok());
+    let res_str = result.unwrap().to_string();
+    // Tests are named after the files
+    assert!(res_str.contains("fn my_test__pm_tests_test_data_json_glob_alice_json"));
+    assert!(res_str.contains("fn my_test__pm_tests_test_data_json_glob_nested_carol_json"));
+    assert!(res_str.contains("MY_TEST_DATA_0"));
+    assert!(res_str.contains("MY_TEST_DATA_2"));
+
+    // Each file is validated against the parameter
+    let attr = quote! { JsonGlob("tests/test_data/json_glob_invalid/*.json") };
+    let item = quote! { fn my_test(v: User) { let _ = v.age; } };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("missing required field `age` in file tests/test_data/json_glob_invalid/dave.json"), "{}", err);
+
+    let attr = quote! { JsonGlob("tests/test_data/json_glob/*.yaml") };
+    let item = quote! { fn my_test(v: User) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("No files matched pattern"));
+}
+
+#[test]
+fn test_json_select() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { JsonFile("tests/test_data/test_suites.json", select = "/suites/login/cases") };
+    let item = quote! { fn my_test(v: LoginCase) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_ok());
+    let res_str = result.unwrap().to_string();
+    assert!(res_str.contains("fn my_test__true_alice"));
+    assert!(res_str.contains("fn my_test__false_mallory"));
+
+    // The error names the segment that did not resolve
+    let attr = quote! { JsonFile("tests/test_data/test_suites.json", select = "/suites/logout/cases") };
+    let item = quote! { fn my_test(v: LoginCase) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Segment `logout` of `/suites/logout/cases` did not resolve: no such key"), "{}", err);
+    assert!(err.contains("`login`"), "{}", err);
+
+    let attr = quote! { JsonString("[[1, 2]]", select = "/0/5") };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("index 5 is out of bounds for array of length 2"));
+
+    let attr = quote! { JsonFile("tests/test_data/test_suites.json", select = "/version/major") };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Segment `major` of `/version/major` did not resolve: cannot index into a number"));
+
+    let attr = quote! { JsonString("[1]", select = "$.data") };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Expected a JSON Pointer starting with `/`"));
+
+    let attr = quote! { JsonString("[1]", pick = "/data") };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Unknown option: pick"));
+
+    // Only the JSON sources take a selector
+    let attr = quote! { YamlString("- 1", select = "/0") };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Unknown option: select"));
+}
+
+#[test]
+fn test_runtime_load() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { JsonFile("tests/test_data/test_ddt_data.json"), load = runtime };
+    let item = quote! { fn my_test(p: Person) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_ok());
+    let res_str = result.unwrap().to_string();
+    assert!(res_str.contains("fn __my_test_impl"));
+    assert!(res_str.contains("fn my_test__rt_tests_test_data_test_ddt_data_json"));
+    assert!(res_str.contains("load_cases"));
+    // The data is neither read nor embedded at compile time
+    assert!(!res_str.contains("include_str"));
+    assert!(!res_str.contains("my_test__30_alice"));
+
+    // A missing file is only reported when the test runs
+    let attr = quote! { JsonFile("tests/test_data/missing.json"), load = runtime };
+    let item = quote! { fn my_test(p: Person) {} };
+    assert!(test_params_source(attr, item).is_ok());
+
+    let attr = quote! { JsonFile("tests/test_data/test_ddt_data.json"), load = lazily };
+    let item = quote! { fn my_test(p: Person) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Expected `compile_time` or `runtime`"));
+
+    let attr = quote! { JsonFile("tests/test_data/test_ddt_data.json"), mode = runtime };
+    let item = quote! { fn my_test(p: Person) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Unknown test_params_source option: mode"));
+}
+
+#[test]
+fn test_runtime_load_cases() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let cases = rust_test_core::runtime::load_cases(
+        r#"JsonFile("tests/test_data/test_ddt_data.json")"#,
+        "fn my_test(p: Person) {}",
+        manifest_dir,
+        None,
+    );
+    assert_eq!(cases.len(), 4);
+    assert_eq!(cases[0].name, "30_alice");
+    assert_eq!(cases[0].input, r#"{"age":30,"name":"Alice"}"#);
+    assert!(cases[0].expected.is_none());
+
+    let result = std::panic::catch_unwind(|| {
+        rust_test_core::runtime::load_cases(r#"JsonFile("tests/test_data/missing.json")"#, "fn my_test(p: Person) {}", manifest_dir, None)
+    });
+    let payload = result.unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.contains("Could not load test data: Could not read file"), "{}", message);
+}
+
+#[test]
+fn test_named_cases() {
+    let attr = quote! { name = "admin_user", User { name: "Richard", age: 50 } };
+    let item = quote! { fn my_test(u: User) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__admin_user"));
+    assert!(res_str.contains("rust_test_seen_name"));
+
+    let attr = quote! { name = "admin user", User { name: "Richard", age: 50 } };
+    let item = quote! { fn my_test(u: User) {} };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Test case name `admin user` must consist of letters, digits and underscores"));
+
+    let attr = quote! { name = "one", 1, 2 };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("`name` can only be used with a single test case"));
+
+    let attr = quote! { title = "one", 1 };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Unknown test_params option: title"));
+
+    // Names already used by a previous attribute are a compile error
+    let attr = quote! { name = "admin", 2 };
+    let item = quote! {
+        #[rust_test_framework::rust_test_seen_name("[\"my_test__admin\"]")]
+        fn __my_test_impl(v: u32) {}
+    };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Duplicate test name `my_test__admin`"));
+
+    // Different values can produce the same name
+    let attr = quote! { "a b", "a_b" };
+    let item = quote! { fn my_test(v: String) {} };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Duplicate test name `my_test__a_b`"));
+}
+
+#[test]
+fn test_name_field() {
+    let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let attr = quote! { JsonFile("tests/test_data/test_ddt_data.json"), name_field = "name" };
+    let item = quote! { fn my_test(p: Person) {} };
+    let res_str = test_params_source(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__alice"));
+    assert!(res_str.contains("fn my_test__carol"));
+
+    let attr = quote! { JsonString(r#"[{"id": 7, "v": 1}, {"v": 2}]"#), name_field = "id" };
+    let item = quote! { fn my_test(p: Case) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Test case has no `id` field to name it after"));
+
+    let attr = quote! { JsonString("[1, 2]"), name_field = "id" };
+    let item = quote! { fn my_test(v: u32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.unwrap_err().to_string().contains("`name_field` requires test cases that are objects"));
+
+    let attr = quote! { JsonString(r#"[{"id": "a-b"}, {"id": "a b"}]"#), name_field = "id" };
+    let item = quote! { fn my_test(p: Case) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Duplicate test name `my_test__a_b`"));
+}
+
+#[test]
+fn test_expect() {
+    let attr = quote! { 2, 3, expect = 5 };
+    let item = quote! { fn my_test(a: i32, b: i32) -> i32 { a + b } };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__2_3"));
+    assert!(res_str.contains("assert_expected"));
+
+    let attr = quote! { 2, 3, expect = 5 };
+    let item = quote! { fn my_test(a: i32, b: i32) {} };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("`expect` requires the test function to return a value"));
+
+    let attr = quote! { 2, 3, expect = 5 };
+    let item = quote! { fn my_test(a: i32) -> i32 { a } };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("`expect` can only be used with a single test case"));
+
+    let attr = quote! { 2, expect = "two" };
+    let item = quote! { fn my_test(a: i32) -> u32 { 2 } };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Expected number for type u32, but got: \"two\""));
+}
+
+#[test]
+fn test_expected_in_source() {
+    let attr = quote! { JsonString(r#"[{"a": 1, "expected": 2}, {"input": {"a": 2}, "expected": 4}]"#) };
+    let item = quote! { fn my_test(v: Case) -> u32 { v.a * 2 } };
+    let res_str = test_params_source(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__1"));
+    assert!(res_str.contains("fn my_test__2"));
+    assert!(res_str.contains("assert_expected"));
+
+    // Without a return value the `expected` field is part of the input
+    let attr = quote! { JsonString(r#"[{"a": 1, "expected": 2}]"#) };
+    let item = quote! { fn my_test(v: Case) {} };
+    let res_str = test_params_source(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__1_2"));
+    assert!(!res_str.contains("assert_expected"));
+
+    let attr = quote! { JsonString(r#"[{"a": 1, "expected": "two"}]"#) };
+    let item = quote! { fn my_test(v: Case) -> u32 { v.a * 2 } };
+    let result = test_params_source(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Invalid expected value: Expected number for type u32"));
+}
+
+#[test]
+fn test_case_markers() {
+    let attr = quote! { 1, 0, should_panic = "divide by zero" };
+    let item = quote! { fn my_test(a: i32, b: i32) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("# [should_panic (expected = \"divide by zero\")]"), "{}", res_str);
+
+    let attr = quote! { -1, ignore = "JIRA-123" };
+    let item = quote! { fn my_test(a: i32) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("# [ignore = \"JIRA-123\"]"), "{}", res_str);
+
+    let attr = quote! { -1, ignore = 1 };
+    let item = quote! { fn my_test(a: i32) {} };
+    let result = test_params(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Expected a string or a boolean"));
+
+    let attr = quote! { JsonString(r#"[{"input": 1, "$ignore": true}, {"input": 2, "$should_panic": "boom"}]"#) };
+    let item = quote! { fn my_test(a: i32) {} };
+    let res_str = test_params_source(attr, item).unwrap().to_string();
+    assert!(res_str.contains("# [ignore] # [allow (non_snake_case)] fn my_test__1"), "{}", res_str);
+    assert!(res_str.contains("# [should_panic (expected = \"boom\")] # [allow (non_snake_case)] fn my_test__2"), "{}", res_str);
+
+    let attr = quote! { JsonString(r#"[{"input": 1, "$skip": true}]"#) };
+    let item = quote! { fn my_test(a: i32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Unknown test case key `$skip`, expected `$ignore` or `$should_panic`"));
+
+    let attr = quote! { JsonString(r#"[{"input": 1, "$ignore": 5}]"#) };
+    let item = quote! { fn my_test(a: i32) {} };
+    let result = test_params_source(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Expected a string or a boolean for `$ignore`, but got: 5"));
+}
+
+#[test]
+fn test_matrix_errors() {
+    let attr = quote! { [1, 2], ["a", "b"] };
+    let item = quote! { fn my_test(a: u32, b: &str) {} };
+    let res_str = test_matrix(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__1_a"));
+    assert!(res_str.contains("fn my_test__2_b"));
+    assert!(res_str.contains("rust_test_seen_value"));
+
+    let attr = quote! { [1, 2] };
+    let item = quote! { fn my_test(a: u32, b: &str) {} };
+    let result = test_matrix(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Test function expects 2 arguments, but 1 value lists were provided in #[test_matrix]"));
+
+    let attr = quote! { [1, 2], "a" };
+    let item = quote! { fn my_test(a: u32, b: &str) {} };
+    let result = test_matrix(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Expected a list of values for each parameter"));
+
+    let attr = quote! { [1, 2], [] };
+    let item = quote! { fn my_test(a: u32, b: &str) {} };
+    let result = test_matrix(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Expected at least one value"));
+
+    // Repeated values produce duplicate combinations
+    let attr = quote! { [1, 1], ["a"] };
+    let item = quote! { fn my_test(a: u32, b: &str) {} };
+    let result = test_matrix(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Duplicate test case value found"));
+}
+
+#[test]
+fn test_matrix_strategy() {
+    let count_tests = |s: &str| s.matches("fn my_test__").count();
+
+    let attr = quote! { [1, 2, 3, 4, 5], [1, 2, 3, 4, 5], [1, 2, 3, 4, 5], [1, 2, 3, 4, 5], [1, 2, 3, 4, 5], [1, 2, 3, 4, 5], strategy = pairwise };
+    let item = quote! { fn my_test(a: u32, b: u32, c: u32, d: u32, e: u32, f: u32) {} };
+    let res_str = test_matrix(attr.clone(), item.clone()).unwrap().to_string();
+    let count = count_tests(&res_str);
+    assert!((25..=45).contains(&count), "{} tests", count);
+    // The same input always produces the same tests
+    assert_eq!(test_matrix(attr, item).unwrap().to_string(), res_str);
+
+    let attr = quote! { [1, 2], [3, 4], [5, 6], strategy = nwise(3) };
+    let item = quote! { fn my_test(a: u32, b: u32, c: u32) {} };
+    assert_eq!(count_tests(&test_matrix(attr, item).unwrap().to_string()), 8);
+
+    let attr = quote! { [1, 2], [3, 4], strategy = random };
+    let item = quote! { fn my_test(a: u32, b: u32) {} };
+    let result = test_matrix(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Expected `full`, `pairwise` or `nwise(N)`"));
+
+    let attr = quote! { [1, 2], [3, 4], strategy = nwise(0) };
+    let item = quote! { fn my_test(a: u32, b: u32) {} };
+    let result = test_matrix(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Expected a strength of at least 1"));
+
+    let attr = quote! { [1, 2], [3, 4], order = pairwise };
+    let item = quote! { fn my_test(a: u32, b: u32) {} };
+    let result = test_matrix(attr, item);
+    assert!(result.unwrap_err().to_string().contains("Unknown test_matrix option: order"));
+}
+
+#[test]
+fn test_generated_source() {
+    let attr = quote! { Generated(strategy = 0..10u32, cases = 50, seed = 42) };
+    let item = quote! { fn my_test(a: u32, s: &str) {} };
+    let res_str = test_params_source(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn __my_test_impl"));
+    assert!(res_str.contains("fn my_test__generated"));
+    assert!(res_str.contains("generated :: run (0 .. 10u32 , 50u32 , Some (42u64)"));
+    // Reference parameters are generated as owned values and borrowed
+    assert!(res_str.contains("| (arg_0 , arg_1 ,) : (u32 , String ,) |"));
+    assert!(res_str.contains("__my_test_impl (arg_0 , & arg_1)"));
+
+    let attr = quote! { Generated };
+    let item = quote! { fn my_test(items: Vec<i32>) {} };
+    let res_str = test_params_source(attr, item).unwrap().to_string();
+    assert!(res_str.contains("generated :: any :: < Vec < i32 > > () , 256u32 , None"));
+
+    let attr = quote! { Generated(cases = 0) };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params_source(attr, item).unwrap_err().to_string().contains("Expected at least one case"));
+
+    let attr = quote! { Generated(runs = 10) };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params_source(attr, item).unwrap_err().to_string().contains("Unknown Generated option: runs"));
+
+    let attr = quote! { Generated::<u32> };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params_source(attr, item).unwrap_err().to_string().contains("Generated takes the types of the test function parameters"));
+
+    let attr = quote! { Generated, name_field = "id" };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params_source(attr, item).unwrap_err().to_string().contains("`name_field` can't be used with generated test cases"));
+}
+
+#[test]
+fn test_range_values() {
+    let attr = quote! { 1..=3 };
+    let item = quote! { fn my_test(a: u32) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__1"));
+    assert!(res_str.contains("fn my_test__3"));
+    assert!(!res_str.contains("fn my_test__4"));
+
+    let attr = quote! { range(0, 10, step = 0) };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params(attr, item).unwrap_err().to_string().contains("Expected a non-zero step"));
+
+    let attr = quote! { 5..5 };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params(attr, item).unwrap_err().to_string().contains("The range is empty"));
+
+    let attr = quote! { 1.. };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params(attr, item).unwrap_err().to_string().contains("Expected a range with a start and an end"));
+
+    let attr = quote! { 1..='z' };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params(attr, item).unwrap_err().to_string().contains("Both ends of a range must be integers or characters"));
+
+    let attr = quote! { range(0, 10, by = 2) };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(test_params(attr, item).unwrap_err().to_string().contains("Expected `step = n`"));
+}
+
+#[test]
+fn test_range_limit() {
+    let attr = quote! { 0..=100000 };
+    let item = quote! { fn my_test(a: u32) {} };
+    let err = test_params(attr, item).unwrap_err().to_string();
+    assert!(err.contains("The range expands into 100001 test cases, more than the limit of 1000"), "{}", err);
+
+    // Each range is small, but their combinations are not
+    let attr = quote! { 0..100, 0..100 };
+    let item = quote! { fn my_test(a: u32, b: u32) {} };
+    let err = test_params(attr, item).unwrap_err().to_string();
+    assert!(err.contains("The ranges expand into 10000 test cases, more than the limit of 1000"), "{}", err);
+
+    let attr = quote! { 0..100, 0..100 };
+    let item = quote! { fn my_test(a: u32, b: u32) {} };
+    let err = test_matrix(attr, item).unwrap_err().to_string();
+    assert!(err.contains("The matrix expands into 10000 test cases"), "{}", err);
+
+    let attr = quote! { 0..10, 0..10, 0..10, 0..10, strategy = pairwise };
+    let item = quote! { fn my_test(a: u32, b: u32, c: u32, d: u32) {} };
+    assert!(test_matrix(attr, item).is_ok());
+
+    // A covering array holds every combination of values of any N dimensions
+    let attr = quote! { 0..100, 0..100, strategy = pairwise };
+    let item = quote! { fn my_test(a: u32, b: u32) {} };
+    let err = test_matrix(attr, item).unwrap_err().to_string();
+    assert!(err.contains("The 2-wise matrix expands into at least 10000 test cases, more than the limit of 1000"), "{}", err);
+
+    let attr = quote! { 0..5, 0..5, 0..5, 0..5, 0..5, 0..5, strategy = nwise(6) };
+    let item = quote! { fn my_test(a: u32, b: u32, c: u32, d: u32, e: u32, f: u32) {} };
+    let err = test_matrix(attr, item).unwrap_err().to_string();
+    assert!(err.contains("The 6-wise matrix expands into at least 15625 test cases"), "{}", err);
+
+    // Over the limit only once the array is built
+    let attr = quote! { 0..10, 0..10, 0..10, 0..2, 0..2, strategy = nwise(3) };
+    let item = quote! { fn my_test(a: u32, b: u32, c: u32, d: u32, e: u32) {} };
+    let err = test_matrix(attr, item).unwrap_err().to_string();
+    assert!(err.contains("The 3-wise matrix expands into 1001 test cases"), "{}", err);
+}
+
+#[test]
+fn test_literal_range_checks() {
+    let cases = [
+        (quote! { 256 }, quote! { fn my_test(a: u8) {} }, "Value 256 is out of range for type u8"),
+        (quote! { -1 }, quote! { fn my_test(a: u64) {} }, "Value -1 is out of range for type u64"),
+        (quote! { 300u8 }, quote! { fn my_test(a: u32) {} }, "Value 300 is out of range for type u8"),
+        (quote! { 1.5 }, quote! { fn my_test(a: u32) {} }, "Expected integer for type u32, but got: 1.5"),
+        (quote! { "ab" }, quote! { fn my_test(a: char) {} }, "Expected a single character for type char"),
+        (
+            quote! { 340282366920938463463374607431768211455 },
+            quote! { fn my_test(a: i128) {} },
+            "Value 340282366920938463463374607431768211455 is out of range for type i128",
+        ),
+        (
+            quote! { 340282366920938463463374607431768211455 },
+            quote! { fn my_test(a: String) {} },
+            "Expected string for type String",
+        ),
+    ];
+    for (attr, item, message) in cases {
+        let err = test_params(attr, item).unwrap_err().to_string();
+        assert!(err.contains(message), "{}", err);
+    }
+
+    // A constant is checked by the compiler
+    let attr = quote! { u64::MAX };
+    let item = quote! { fn my_test(a: u64) {} };
+    assert!(test_params(attr, item).unwrap().to_string().contains("__my_test_impl (u64 :: MAX)"));
+
+    let attr = quote! { 18446744073709551615, -128i8 };
+    let item = quote! { fn my_test(a: u64, b: i8) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("[18446744073709551615,-128]"), "{}", res_str);
+}
+
+#[test]
+fn test_collection_and_tuple_struct_values() {
+    let attr = quote! { Meters(5), Kind::Large(5), [1, 2], vec![(1, "a")] };
+    let item = quote! { fn my_test(a: Meters, b: Kind, c: Vec<u32>, d: Vec<(u32, String)>) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    // A tuple struct is its inner value, an enum variant is tagged with its name
+    assert!(res_str.contains(r#"[5,{\"Large\":5},[1,2],[[1,\"a\"]]]"#), "{}", res_str);
+    assert!(res_str.contains("fn my_test__meters_5_large_5_1_2_1_a"));
+
+    // A bare call that doesn't name the parameter type is an imported enum variant
+    let attr = quote! { Circle(1.5), Some(Meters(2)) };
+    let item = quote! { fn my_test(a: Shape, b: Option<Meters>) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains(r#"[{\"Circle\":1.5},2]"#), "{}", res_str);
+
+    let attr = quote! { &[1, 2] };
+    let item = quote! { fn my_test(v: &[u32]) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("let data : Vec < u32 >"));
+    assert!(res_str.contains("__my_test_impl (& data)"));
+
+    let attr = quote! { vec![0; 100000] };
+    let item = quote! { fn my_test(v: Vec<u32>) {} };
+    assert!(test_params(attr, item).unwrap_err().to_string().contains("Expected a length of at most 1000"));
+
+    let attr = quote! { [0; N] };
+    let item = quote! { fn my_test(v: Vec<u32>) {} };
+    assert!(test_params(attr, item).unwrap_err().to_string().contains("Expected an integer literal as the length"));
+}
+
+#[test]
+fn test_test_case_errors() {
+    let cases = vec![
+        (
+            quote! { 1 },
+            quote! { fn my_test(a: u32, b: u32) {} },
+            "Test function expects 2 arguments, but 1 were provided in #[test_case]",
+        ),
+        (
+            quote! { 1, timeout = 5 },
+            quote! { fn my_test(a: u32) {} },
+            "Unknown test_case option: timeout",
+        ),
+        (
+            quote! { 1, expect = 2 },
+            quote! { fn my_test(a: u32) {} },
+            "`expect` requires the test function to return a value",
+        ),
+    ];
+    for (attr, item, message) in cases {
+        let err = test_case(attr, item).unwrap_err().to_string();
+        assert!(err.contains(message), "{}", err);
+    }
+
+    let attr = quote! { Duration::from_secs(3), name = "three" };
+    let item = quote! { fn my_test(d: Duration) {} };
+    let res_str = test_case(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__three"));
+    assert!(res_str.contains("__my_test_impl (Duration :: from_secs (3))"), "{}", res_str);
+
+    let attr = quote! { Duration::from_secs(3), expect = Duration::from_secs(3) };
+    let item = quote! { fn my_test(d: Duration) -> Duration { d } };
+    let res_str = test_case(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__case_1"));
+    assert!(res_str.contains("let expected : Duration = Duration :: from_secs (3)"), "{}", res_str);
+
+    // Stacked test cases are duplicates if their expressions are the same
+    let attr = quote! { Duration::from_secs(3) };
+    let item = quote! {
+        #[rust_test_framework::rust_test_seen_expr("[\"Duration :: from_secs (3)\"]")]
+        fn my_test(d: Duration) {}
+    };
+    let err = test_case(attr, item).unwrap_err().to_string();
+    assert!(err.contains("Duplicate test case value found"), "{}", err);
+
+    // But not if a value of another attribute happens to spell out the same expression
+    let attr = quote! { "x" };
+    let item = quote! {
+        #[rust_test_framework::rust_test_seen_value("[\"\\u0000expr:\\\"x\\\"\", \"\\\"x\\\"\"]")]
+        fn my_test(s: &str) {}
+    };
+    assert!(test_case(attr, item).is_ok());
+}
+
+#[test]
+fn test_constant_values() {
+    let attr = quote! { FIXTURE_A, 5 };
+    let item = quote! { fn my_test(a: Fixture, b: u32) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__fixture_a_5"));
+    assert!(res_str.contains("let arg_1 : u32 = rust_test_framework :: __private :: serde_json :: from_str (\"5\")"), "{}", res_str);
+    assert!(res_str.contains("__my_test_impl (FIXTURE_A , arg_1)"), "{}", res_str);
+
+    // A unit variant is still passed by name
+    let attr = quote! { Kind::Small };
+    let item = quote! { fn my_test(a: Kind) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__small"));
+    assert!(res_str.contains("let data : Kind"), "{}", res_str);
+
+    // So is an all-caps variant, unless the prefix says otherwise
+    let attr = quote! { Method::GET };
+    let item = quote! { fn my_test(a: Method) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__get"));
+    assert!(res_str.contains(r#"from_str ("\"GET\"")"#), "{}", res_str);
+
+    let attr = quote! { const: Limits::DEFAULT };
+    let item = quote! { fn my_test(a: Limits) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("__my_test_impl (Limits :: DEFAULT)"), "{}", res_str);
+
+    let attr = quote! { const: paths::root };
+    let item = quote! { fn my_test(a: PathBuf) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("fn my_test__paths_root"));
+    assert!(res_str.contains("__my_test_impl (paths :: root)"), "{}", res_str);
+
+    let cases = vec![
+        (
+            quote! { vec![LIMIT, 2] },
+            quote! { fn my_test(a: Vec<u32>) {} },
+            "Constants can only be passed as a whole argument",
+        ),
+        (
+            quote! { Point { x: ORIGIN_X, y: 0 } },
+            quote! { fn my_test(a: Point) {} },
+            "Constants can only be passed as a whole argument",
+        ),
+        (
+            quote! { const: 5 },
+            quote! { fn my_test(a: u32) {} },
+            "Expected the path of a constant or static",
+        ),
+    ];
+    for (attr, item, message) in cases {
+        let err = test_params(attr, item).unwrap_err().to_string();
+        assert!(err.contains(message), "{}", err);
+    }
+}
+
+#[test]
+fn test_async_tests() {
+    let attr = quote! { 1 };
+    let item = quote! { async fn my_test(a: u32) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("runtime :: block_on (async {"), "{}", res_str);
+    assert!(res_str.contains("__my_test_impl (data) . await"), "{}", res_str);
+
+    let attr = quote! { 1, runtime = "tokio::test(flavor = \"multi_thread\")" };
+    let item = quote! { async fn my_test(a: u32) {} };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("# [tokio :: test (flavor = \"multi_thread\")]"), "{}", res_str);
+    assert!(res_str.contains("async fn my_test__1 ()"), "{}", res_str);
+    // Attributes stacked below use the same runtime
+    assert!(res_str.contains("# [rust_test_framework :: rust_test_runtime (tokio :: test"), "{}", res_str);
+
+    let cases = vec![
+        (
+            quote! { 1, runtime = "tokio::test" },
+            quote! { fn my_test(a: u32) {} },
+            "`runtime` requires an async test function",
+        ),
+        (
+            quote! { 1, runtime = "not a path" },
+            quote! { async fn my_test(a: u32) {} },
+            "Expected the test attribute of an async runtime",
+        ),
+    ];
+    for (attr, item, message) in cases {
+        let err = test_params(attr, item).unwrap_err().to_string();
+        assert!(err.contains(message), "{}", err);
+    }
+
+    let attr = quote! { JsonString("[1]"), load = runtime, runtime = "tokio::test" };
+    let item = quote! { async fn my_test(a: u32) {} };
+    let err = test_params_source(attr, item).unwrap_err().to_string();
+    assert!(err.contains("`runtime` can only be used with test cases loaded at compile time"), "{}", err);
+}
+
+#[test]
+fn test_returned_results() {
+    let attr = quote! { 1, 2 };
+    let item = quote! { fn my_test(a: u32, b: u32) -> Result<(), String> { Ok(()) } };
+    let res_str = test_params(attr, item).unwrap().to_string();
+    assert!(res_str.contains("let actual = __my_test_impl (arg_0 , arg_1) ;"), "{}", res_str);
+    assert!(res_str.contains("Returned :: new (actual)) . assert_success (\"[1,2]\")"), "{}", res_str);
+
+    // Without a return value there is nothing to check
+    let attr = quote! { 1 };
+    let item = quote! { fn my_test(a: u32) {} };
+    assert!(!test_params(attr, item).unwrap().to_string().contains("assert_success"));
+
+    let attr = quote! { JsonString("[1]"), load = runtime };
+    let item = quote! { fn my_test(a: u32) -> Result<(), String> { Ok(()) } };
+    let res_str = test_params_source(attr, item).unwrap().to_string();
+    assert!(res_str.contains("Returned :: new (actual)) . assert_expected_json (expected)"), "{}", res_str);
+    assert!(res_str.contains("Returned :: new (actual)) . assert_success (json)"), "{}", res_str);
+
+    use rust_test_core::runtime::{run_cases, Case, CheckResult as _, Returned};
+    let cases = ["\"1\"", "\"x\""]
+        .iter()
+        .map(|input| Case {
+            name: input.trim_matches('"').to_string(),
+            input: input.to_string(),
+            expected: None,
+            ignore: None,
+            should_panic: None,
+            csv_row: None,
+        })
+        .collect();
+    let result = std::panic::catch_unwind(|| {
+        run_cases(cases, |case| {
+            let json = case.input.as_str();
+            let input: String = serde_json::from_str(json).unwrap();
+            (&&&Returned::new(input.parse::<u32>())).assert_success(json);
+        })
+    });
+    let message = result.unwrap_err().downcast_ref::<String>().cloned().unwrap();
+    assert!(message.contains("1 of 2 test cases failed"), "{}", message);
+    assert!(message.contains("x: Test case returned Err(ParseIntError { kind: InvalidDigit }) for input: \"x\""), "{}", message);
+}
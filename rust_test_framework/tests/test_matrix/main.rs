@@ -0,0 +1,110 @@
+use rust_test_framework::{test_fixture, test_matrix, test_params, setup};
+use serde::Deserialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[test_matrix([1, 2, 3], ["a", "b"], [true, false])]
+fn test_full_cross_product(id: u32, label: &str, enabled: bool) {
+    assert!(id > 0);
+    assert!(!label.is_empty());
+    let _ = enabled;
+}
+
+#[test]
+fn test_cross_product_names() {
+    // Fails to compile if any combination is missing
+    let _ = test_full_cross_product__1_a_true;
+    let _ = test_full_cross_product__1_a_false;
+    let _ = test_full_cross_product__1_b_true;
+    let _ = test_full_cross_product__1_b_false;
+    let _ = test_full_cross_product__2_a_true;
+    let _ = test_full_cross_product__2_a_false;
+    let _ = test_full_cross_product__2_b_true;
+    let _ = test_full_cross_product__2_b_false;
+    let _ = test_full_cross_product__3_a_true;
+    let _ = test_full_cross_product__3_a_false;
+    let _ = test_full_cross_product__3_b_true;
+    let _ = test_full_cross_product__3_b_false;
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Browser {
+    Chrome,
+    Firefox,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Role {
+    Admin,
+    Guest(u32),
+}
+
+#[test_matrix([Browser::Chrome, Browser::Firefox], ["en_US", "de_DE"], [Role::Admin, Role::Guest(1)])]
+fn test_enum_dimensions(browser: Browser, locale: &str, role: Role) {
+    assert!(locale.contains('_'));
+    assert!(browser == Browser::Chrome || browser == Browser::Firefox);
+    assert_ne!(role, Role::Guest(0));
+}
+
+#[test_matrix([1, 2, 3])]
+fn test_single_dimension(v: u32) {
+    assert!(v <= 3);
+}
+
+#[test_matrix([10, 20], [1, 2])]
+#[test_params(0, 0)]
+fn test_matrix_with_test_params(a: u32, b: u32) {
+    assert!(a >= b);
+}
+
+#[test_fixture]
+mod fixture {
+    use super::*;
+
+    static SETUP_RUNS: AtomicU32 = AtomicU32::new(0);
+
+    #[setup]
+    fn set_up() {
+        SETUP_RUNS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test_matrix([1, 2], [3, 4])]
+    fn test_matrix_in_fixture(a: u32, b: u32) {
+        assert!(SETUP_RUNS.load(Ordering::SeqCst) > 0);
+        assert!(a < b);
+    }
+}
+
+#[test_matrix(
+    [Browser::Chrome, Browser::Firefox],
+    ["en_US", "de_DE", "fr_FR"],
+    [Role::Admin, Role::Guest(1), Role::Guest(2)],
+    [true, false],
+    strategy = pairwise
+)]
+fn test_pairwise(browser: Browser, locale: &str, role: Role, dark_mode: bool) {
+    assert!(browser == Browser::Chrome || browser == Browser::Firefox);
+    assert_eq!(locale.len(), 5);
+    assert_ne!(role, Role::Guest(0));
+    let _ = dark_mode;
+}
+
+#[test_matrix(strategy = nwise(3), [1, 2], [3, 4], [5, 6], [7, 8])]
+fn test_three_wise(a: u32, b: u32, c: u32, d: u32) {
+    assert!(a < b && b < c && c < d);
+}
+
+#[test_matrix([1, 2], [3, 4], strategy = full)]
+fn test_explicit_full(a: u32, b: u32) {
+    assert!(a < b);
+}
+
+#[test_matrix(1..=3, ['a'..='b', 'y'..='z'], [range(0, 100, step = 50)])]
+fn test_range_dimensions(count: u32, letter: char, offset: u32) {
+    assert!(count >= 1 && letter.is_ascii_lowercase() && offset < 100);
+}
+
+#[test]
+fn test_range_dimension_tests_exist() {
+    let _ = test_range_dimensions__1_a_0;
+    let _ = test_range_dimensions__3_z_50;
+}
@@ -0,0 +1,83 @@
+use rust_test_framework::test_params;
+use serde::Deserialize;
+
+#[test_params([1, 2, 3], 6)]
+#[test_params(vec![10, -4], 6)]
+#[test_params(vec![2; 3], 6)]
+#[test_params([], 0)]
+fn test_vec_params(values: Vec<i32>, sum: i32) {
+    assert_eq!(values.iter().sum::<i32>(), sum);
+}
+
+#[test_params(&[1, 2, 3])]
+#[test_params([0; 4])]
+fn test_slice_params(values: &[u8]) {
+    assert!(values.len() >= 3);
+}
+
+#[test_params(&"borrowed")]
+fn test_reference_params(text: &str) {
+    assert_eq!(text, "borrowed");
+}
+
+#[test_params(((1, 2), "pair"))]
+fn test_nested_tuples(value: ((u32, u32), String)) {
+    assert_eq!(value.0 .0 + value.0 .1, 3);
+    assert_eq!(value.1, "pair");
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Meters(u32);
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Point(i32, i32);
+
+#[test_params(Meters(5), Point(1, -1))]
+fn test_tuple_structs(distance: Meters, point: Point) {
+    assert_eq!(distance, Meters(5));
+    assert_eq!(point.0 + point.1, 0);
+}
+
+#[test_params(Some(Meters(3)), vec![Meters(1), Meters(2)])]
+fn test_nested_tuple_structs(limit: Option<Meters>, distances: Vec<Meters>) {
+    assert_eq!(limit, Some(Meters(3)));
+    assert_eq!(distances, [Meters(1), Meters(2)]);
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Shape {
+    Circle(f64),
+    Square(f64),
+}
+
+use Shape::*;
+
+#[test_params(Circle(1.0))]
+#[test_params(Square(2.0))]
+fn test_imported_enum_variants(shape: Shape) {
+    assert!(matches!(shape, Circle(r) if r == 1.0) || shape == Square(2.0));
+}
+
+#[derive(Deserialize, Debug)]
+struct Route {
+    stops: Vec<String>,
+    length: Meters,
+}
+
+// Field types are unknown, so a tuple struct field takes its inner value
+#[test_params(Route { stops: vec!["a", "b"], length: 12 })]
+fn test_collections_in_structs(route: Route) {
+    assert_eq!(route.stops, ["a", "b"]);
+    assert_eq!(route.length.0, 12);
+}
+
+#[test]
+fn test_collection_tests_exist() {
+    let _ = test_vec_params__1_2_3_6;
+    let _ = test_vec_params__2_2_2_6;
+    let _ = test_vec_params__empty_0;
+    let _ = test_slice_params__0_0_0_0;
+    let _ = test_nested_tuples__1_2_pair;
+    let _ = test_tuple_structs__meters_5_point_1_neg_1;
+    let _ = test_imported_enum_variants__circle_1_0;
+}
@@ -0,0 +1,103 @@
+use rust_test_framework::{test_matrix, test_params};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Not deserializable, so it can only be passed as a constant.
+#[derive(Debug, PartialEq)]
+struct Limits {
+    timeout: Duration,
+    retries: u32,
+}
+
+const FIXTURE_A: Limits = Limits { timeout: Duration::from_secs(1), retries: 3 };
+static NAMES: [&str; 2] = ["alice", "bob"];
+#[allow(non_upper_case_globals)]
+const default_retries: u32 = 3;
+
+mod consts {
+    pub const LIMITS: [u32; 3] = [1, 2, 3];
+}
+
+#[test_params(FIXTURE_A)]
+fn test_constant(limits: Limits) {
+    assert_eq!(limits.retries, 3);
+}
+
+#[test_params(consts::LIMITS, "three")]
+#[test_params([3, 2, 1], "reversed")]
+fn test_constant_path(limits: [u32; 3], label: &str) {
+    assert_eq!(limits.iter().sum::<u32>(), 6, "{}", label);
+}
+
+#[test_params(&NAMES)]
+fn test_static_reference(names: &[&str; 2]) {
+    assert_eq!(names[0], "alice");
+}
+
+#[test_params(&consts::LIMITS)]
+#[test_params(&[4, 5])]
+fn test_constant_slice(values: &[u32]) {
+    assert!(!values.is_empty());
+}
+
+#[test_params(u8::MAX, i8::MIN)]
+fn test_associated_constants(max: u8, min: i8) {
+    assert_eq!(max as i32 + min as i32, 127);
+}
+
+#[test_params(const: default_retries)]
+fn test_explicit_constant(retries: u32) {
+    assert_eq!(retries, 3);
+}
+
+impl Limits {
+    const DEFAULT: Limits = Limits { timeout: Duration::from_secs(5), retries: 1 };
+}
+
+#[test_params(const: Limits::DEFAULT)]
+fn test_associated_constant_of_type(limits: Limits) {
+    assert_eq!(limits.retries, 1);
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Deserialize, Debug, PartialEq)]
+enum Method {
+    GET,
+    POST,
+}
+
+#[test_params(Method::GET)]
+#[test_params(Method::POST)]
+fn test_all_caps_variants(method: Method) {
+    assert!(method == Method::GET || method == Method::POST);
+}
+
+#[test_params(Some(FIXTURE_A))]
+fn test_optional_constant(limits: Option<Limits>) {
+    assert_eq!(limits, Some(FIXTURE_A));
+}
+
+#[test_params(1, expect = FIXTURE_A)]
+fn test_expected_constant(seconds: u64) -> Limits {
+    Limits { timeout: Duration::from_secs(seconds), retries: 3 }
+}
+
+#[test_matrix([u32::MIN, u32::MAX], [true, false])]
+fn test_matrix_constants(value: u32, flag: bool) {
+    let _ = (value, flag);
+}
+
+#[test]
+fn test_constant_names() {
+    // Fails to compile if the tests are missing
+    let _ = test_constant__fixture_a;
+    let _ = test_constant_path__consts_limits_three;
+    let _ = test_constant_path__3_2_1_reversed;
+    let _ = test_static_reference__names;
+    let _ = test_associated_constants__u8_max_i8_min;
+    let _ = test_explicit_constant__default_retries;
+    let _ = test_associated_constant_of_type__limits_default;
+    let _ = test_all_caps_variants__get;
+    let _ = test_optional_constant__fixture_a;
+    let _ = test_matrix_constants__u32_min_true;
+}
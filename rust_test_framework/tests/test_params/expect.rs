@@ -0,0 +1,42 @@
+use rust_test_framework::test_params;
+use serde::Deserialize;
+
+#[test_params(2, 3, expect = 5)]
+#[test_params(-1, 1, expect = 0)]
+fn test_expect_sum(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[test_params("hello", expect = "HELLO")]
+#[test_params(name = "empty", "", expect = "")]
+fn test_expect_string(input: &str) -> String {
+    input.to_uppercase()
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test_params(Point { x: 1, y: 2 }, expect = Point { x: 2, y: 1 })]
+fn test_expect_struct(p: Point) -> Point {
+    Point { x: p.y, y: p.x }
+}
+
+#[test_params(4, expect = Some(2))]
+#[test_params(-4, expect = None)]
+fn test_expect_option(v: i32) -> Option<u32> {
+    (v >= 0).then(|| (v as f64).sqrt() as u32)
+}
+
+#[test]
+fn test_expect_mismatch_shows_diff() {
+    let result = std::panic::catch_unwind(|| {
+        rust_test_framework::__private::runtime::assert_expected(&Point { x: 1, y: 3 }, &Point { x: 1, y: 2 });
+    });
+    let payload = result.unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("Returned value does not match the expected value"), "{}", message);
+    assert!(message.contains("-     y: 2,\n+     y: 3,"), "{}", message);
+}
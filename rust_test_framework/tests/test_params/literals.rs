@@ -0,0 +1,49 @@
+use rust_test_framework::test_params;
+
+#[test_params('a', 97)]
+#[test_params('é', 233)]
+fn test_char_literals(c: char, code: u32) {
+    assert_eq!(c as u32, code);
+}
+
+#[test_params(b'A', 65)]
+fn test_byte_literals(byte: u8, expected: u8) {
+    assert_eq!(byte, expected);
+}
+
+#[test_params(b"\x01\x02\xff", 3)]
+fn test_byte_string_literals(bytes: Vec<u8>, len: usize) {
+    assert_eq!(bytes.len(), len);
+    assert_eq!(bytes[2], 0xff);
+}
+
+#[test_params(18446744073709551615, "18446744073709551615")]
+#[test_params(0x10, "16")]
+fn test_u64_literals(value: u64, text: &str) {
+    assert_eq!(value.to_string(), text);
+}
+
+#[test_params(340282366920938463463374607431768211455, "340282366920938463463374607431768211455")]
+fn test_u128_literals(value: u128, text: &str) {
+    assert_eq!(value, u128::MAX);
+    assert_eq!(value.to_string(), text);
+}
+
+#[test_params(-170141183460469231731687303715884105728)]
+#[test_params(170141183460469231731687303715884105727)]
+#[test_params(-9223372036854775809)]
+fn test_i128_literals(value: i128) {
+    assert!(value == i128::MIN || value == i128::MAX || value == i64::MIN as i128 - 1);
+}
+
+#[test_params(10u8, -128i8, 1_000u16)]
+fn test_suffixed_literals(a: u8, b: i8, c: u16) {
+    assert_eq!((a, b, c), (10, i8::MIN, 1000));
+}
+
+#[test]
+fn test_literal_tests_exist() {
+    let _ = test_char_literals__a_97;
+    let _ = test_i128_literals__neg_170141183460469231731687303715884105728;
+    let _ = test_u128_literals__340282366920938463463374607431768211455_340282366920938463463374607431768211455;
+}
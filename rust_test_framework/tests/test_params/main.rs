@@ -1,3 +1,11 @@
 mod simple;
 mod structs;
 mod enums;
+mod named;
+mod expect;
+mod markers;
+mod ranges;
+mod literals;
+mod collections;
+mod constants;
+mod results;
@@ -0,0 +1,23 @@
+use rust_test_framework::test_params;
+
+fn divide(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        panic!("divide by zero");
+    }
+    a / b
+}
+
+#[test_params(6, 3)]
+#[test_params(1, 0, should_panic = "divide by zero")]
+// Spelled out as a literal so the case only depends on the marker, constants are covered in constants.rs
+#[test_params(-2147483648, -1, should_panic = "attempt to divide with overflow")]
+fn test_divide(a: i32, b: i32) {
+    divide(a, b);
+}
+
+#[test_params(1)]
+#[test_params(-1, ignore = "negative input is not supported yet")]
+#[test_params(-2, ignore = true)]
+fn test_ignored_cases(v: i32) {
+    assert!(v > 0);
+}
@@ -0,0 +1,36 @@
+use rust_test_framework::test_params;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct User {
+    name: String,
+    role: Role,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Role {
+    admin: bool,
+    level: u32,
+}
+
+#[test_params(name = "admin_user", User { name: "Richard", role: Role { admin: true, level: 3 } })]
+#[test_params(User { name: "Patrick", role: Role { admin: false, level: 1 } }, name = "regular_user")]
+fn test_named_struct(user: User) {
+    assert_eq!(user.role.admin, user.role.level > 1);
+}
+
+#[test_params(name = "empty", "", 0)]
+#[test_params(name = "ascii", "abc", 3)]
+#[test_params("äö", 2)]
+fn test_named_multiple_params(input: &str, chars: usize) {
+    assert_eq!(input.chars().count(), chars);
+}
+
+#[test]
+fn test_named_tests_exist() {
+    // Fails to compile if the names were not applied
+    let _ = test_named_struct__admin_user;
+    let _ = test_named_struct__regular_user;
+    let _ = test_named_multiple_params__empty;
+    let _ = test_named_multiple_params__ascii;
+}
@@ -0,0 +1,46 @@
+use rust_test_framework::test_params;
+
+#[test_params(1..=5)]
+fn test_inclusive_range(value: u32) {
+    assert!((1..=5).contains(&value));
+}
+
+#[test_params(-2..2)]
+fn test_exclusive_range(value: i32) {
+    assert!((-2..2).contains(&value));
+}
+
+#[test_params(range(0, 1000, step = 250))]
+#[test_params(range(10, 0, step = -5))]
+fn test_stepped_range(value: u32) {
+    assert_eq!(value % 5, 0);
+}
+
+#[test_params('a'..='c')]
+fn test_char_range(letter: char) {
+    assert!(letter.is_ascii_lowercase());
+}
+
+#[test_params(1..=3, "x")]
+#[test_params(1..=2, 'y'..='z')]
+fn test_ranges_in_multiple_params(count: usize, label: String) {
+    assert!(count > 0);
+    assert_eq!(label.len(), 1);
+}
+
+#[test]
+fn test_range_tests_exist() {
+    // Fails to compile if the ranges were not expanded into separate tests
+    let _ = test_inclusive_range__1;
+    let _ = test_inclusive_range__5;
+    let _ = test_exclusive_range___2;
+    let _ = test_exclusive_range__1;
+    let _ = test_stepped_range__0;
+    let _ = test_stepped_range__750;
+    let _ = test_stepped_range__10;
+    let _ = test_stepped_range__5;
+    let _ = test_char_range__a;
+    let _ = test_char_range__c;
+    let _ = test_ranges_in_multiple_params__3_x;
+    let _ = test_ranges_in_multiple_params__2_z;
+}
@@ -0,0 +1,110 @@
+use rust_test_framework::{teardown, test_case, test_fixture, test_matrix, test_params};
+use std::fmt;
+use std::num::ParseIntError;
+use std::process::ExitCode;
+
+fn parse(input: &str) -> Result<i32, ParseIntError> {
+    input.parse()
+}
+
+#[test_params("1")]
+#[test_params("-20")]
+#[test_params("x", should_panic = r#"Test case returned Err(ParseIntError { kind: InvalidDigit }) for input: "x""#)]
+fn test_question_mark(input: &str) -> Result<(), ParseIntError> {
+    let value = parse(input)?;
+    assert!(value != 0);
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[test_params(1, 2)]
+#[test_params(2, 1, should_panic = r#"Test case returned Err(Error("2 > 1")) for input: [2,1]"#)]
+fn test_boxed_error(a: u32, b: u32) -> Result<(), Box<dyn std::error::Error>> {
+    if a > b {
+        return Err(Box::new(Error(format!("{} > {}", a, b))));
+    }
+    Ok(())
+}
+
+const LIMIT: u32 = 10;
+
+#[test_params(LIMIT, 5, should_panic = r#"Test case returned Err("5 is below 10") for input: [LIMIT,5]"#)]
+fn test_error_with_constant(limit: u32, value: u32) -> Result<(), String> {
+    if value < limit {
+        return Err(format!("{} is below {}", value, limit));
+    }
+    Ok(())
+}
+
+#[test_matrix(["1", "2"], [true])]
+fn test_matrix_result(input: &str, flag: bool) -> Result<(), ParseIntError> {
+    assert!(flag);
+    parse(input).map(|_| ())
+}
+
+#[test_case("1".to_string())]
+#[test_case("one".to_string(), should_panic = r#"Test case returned Err(ParseIntError { kind: InvalidDigit }) for input: "one""#)]
+fn test_case_result(input: String) -> Result<(), ParseIntError> {
+    parse(&input).map(|_| ())
+}
+
+#[test_params(0)]
+#[test_params(1, should_panic = "Test case returned a failure for input: 1")]
+fn test_exit_code(code: u8) -> ExitCode {
+    ExitCode::from(code)
+}
+
+#[test_params("1", expect = Ok(1))]
+#[test_params("3")]
+fn test_expected_result(input: &str) -> Result<i32, String> {
+    parse(input).map_err(|e| e.to_string())
+}
+
+// Values that can't report a failure are ignored without `expect`
+#[test_params(2, expect = 4)]
+#[test_params(3)]
+fn test_plain_value(value: u32) -> u32 {
+    value * 2
+}
+
+#[test_params("7")]
+#[test_params("x", should_panic = "Test case returned Err(")]
+async fn test_async_result(input: &str) -> Result<(), ParseIntError> {
+    parse(input).map(|_| ())
+}
+
+#[test_fixture]
+mod fixture {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static TEARDOWN_COUNT: AtomicU32 = AtomicU32::new(0);
+
+    #[teardown]
+    fn count_teardown() {
+        TEARDOWN_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test_params("5")]
+    #[test_params("five", should_panic = "Test case returned Err(")]
+    fn test_result_with_teardown(input: &str) -> Result<(), ParseIntError> {
+        parse(input)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_plain_result_with_teardown() -> Result<(), ParseIntError> {
+        parse("9")?;
+        Ok(())
+    }
+}
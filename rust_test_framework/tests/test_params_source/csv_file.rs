@@ -0,0 +1,65 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct User {
+    name: String,
+    age: u32,
+    nickname: Option<String>,
+    score: f64,
+}
+
+#[test_params_source(CsvFile("tests/test_data/test_users.csv"))]
+fn test_csv_header_to_fields(user: User) {
+    assert!(!user.name.is_empty());
+    assert!(user.age > 0);
+    assert!(user.score > 0.0);
+    if user.name == "Bob" {
+        assert!(user.nickname.is_none());
+    } else {
+        assert!(user.nickname.is_some());
+    }
+}
+
+#[test_params_source(CsvFile("tests/test_data/test_params_semicolon.csv", delimiter = ';', quote = '\''))]
+fn test_csv_positional_params(id: u32, label: String, active: bool) {
+    assert!(id > 0);
+    match id {
+        1 => assert_eq!(label, "one; the first"),
+        2 => assert_eq!(label, "two"),
+        _ => assert!(label.is_empty() && active),
+    }
+}
+
+#[test_params_source(CsvFile("tests/test_data/test_single_column.csv"))]
+fn test_csv_single_column(value: u64) {
+    assert_eq!(value % 10, 0);
+}
+
+#[derive(Deserialize, Debug)]
+struct Account {
+    owner: String,
+    balance: u128,
+    debt: i128,
+    note: String,
+}
+
+// Cells are converted by the type of their field
+#[test_params_source(CsvFile("tests/test_data/test_accounts.csv"))]
+fn test_csv_struct_field_types(account: Account) {
+    if account.owner == "Alice" {
+        assert_eq!(account.balance, u128::MAX);
+        assert_eq!(account.debt, i128::MIN);
+        assert_eq!(account.note, "");
+    } else {
+        assert_eq!((account.balance, account.debt), (1, -1));
+        assert_eq!(account.note, "1.50");
+    }
+}
+
+#[test_params_source(CsvFile("tests/test_data/test_accounts.csv"))]
+fn test_csv_positional_128_bit_integers(owner: String, balance: u128, debt: i128, note: String) {
+    assert!(!owner.is_empty());
+    assert!(balance > 0 && debt < 0);
+    assert!(note.is_empty() || note == "1.50");
+}
@@ -0,0 +1,31 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct Sum {
+    a: i32,
+    b: i32,
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_expected.json"))]
+fn test_expected_field(sum: Sum) -> i32 {
+    sum.a + sum.b
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_expected_params.json"))]
+fn test_expected_envelope(input: String, skip: usize) -> String {
+    input[skip..].to_string()
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_expected.json"), load = runtime)]
+fn test_expected_runtime(sum: Sum) -> i32 {
+    sum.a + sum.b
+}
+
+#[test_params_source(YamlString(r#"
+- {a: 1, b: 1, expected: 2}
+- {a: 2, b: 2, expected: 4}
+"#))]
+fn test_expected_yaml(sum: Sum) -> i64 {
+    (sum.a + sum.b) as i64
+}
@@ -0,0 +1,54 @@
+use rust_test_framework::generated::{any, just, run, string, vec};
+use rust_test_framework::test_params_source;
+use std::panic::catch_unwind;
+
+#[test_params_source(Generated)]
+fn test_generated_default(items: Vec<i32>) {
+    let reversed: Vec<i32> = items.iter().rev().rev().cloned().collect();
+    assert_eq!(reversed, items);
+}
+
+#[test_params_source(Generated(strategy = 1..=100u32, cases = 50))]
+fn test_generated_range(value: u32) {
+    assert!((1..=100).contains(&value));
+}
+
+#[test_params_source(Generated(strategy = (string('a'..='z', 1..=8), 0..120u8), seed = 42))]
+fn test_generated_multiple_params(name: &str, age: u8) {
+    assert!(name.chars().all(|c| c.is_ascii_lowercase()));
+    assert!(!name.is_empty() && name.len() <= 8);
+    assert!(age < 120);
+}
+
+#[test_params_source(Generated(strategy = (vec(any::<u8>(), 2..=4), just(true)), cases = 20))]
+fn test_generated_slice(bytes: &[u8], flag: bool) {
+    assert!((2..=4).contains(&bytes.len()));
+    assert!(flag);
+}
+
+fn failure_message(test: impl FnOnce() + std::panic::UnwindSafe) -> String {
+    let payload = catch_unwind(test).unwrap_err();
+    payload.downcast_ref::<String>().cloned().unwrap()
+}
+
+#[test]
+fn test_generated_shrinks_numbers() {
+    let message = failure_message(|| run(0..1000u32, 256, Some(7), |x| assert!(x < 10, "too big")));
+    assert!(message.contains("too big"), "{}", message);
+    assert!(message.contains("minimal input: 10 "), "{}", message);
+    assert!(message.contains("seed: 7 (set RUST_TEST_SEED=7 to replay)"), "{}", message);
+}
+
+#[test]
+fn test_generated_shrinks_collections() {
+    let message = failure_message(|| run(vec(0..100u8, 0..=20), 256, Some(1), |v| assert!(v.len() < 3)));
+    assert!(message.contains("minimal input: [0, 0, 0] "), "{}", message);
+}
+
+#[test]
+fn test_generated_same_seed_same_failure() {
+    let failing = |(a, b): (i64, i64)| assert!(a.checked_add(b).is_some());
+    let first = failure_message(|| run(any::<(i64, i64)>(), 256, Some(3), failing));
+    let second = failure_message(|| run(any::<(i64, i64)>(), 256, Some(3), failing));
+    assert_eq!(first, second);
+}
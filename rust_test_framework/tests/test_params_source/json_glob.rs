@@ -0,0 +1,19 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+#[test_params_source(JsonGlob("tests/test_data/json_glob/*.json"))]
+fn test_json_glob(user: User) {
+    assert!(user.name == "Alice" || user.name == "Bob");
+    assert!(user.age > 0);
+}
+
+#[test_params_source(JsonGlob::<User>("tests/test_data/json_glob/**/*.json"))]
+fn test_json_glob_recursive(user: User) {
+    assert!(["Alice", "Bob", "Carol"].contains(&user.name.as_str()));
+}
@@ -0,0 +1,21 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct Request {
+    method: String,
+    path: String,
+    status: u16,
+}
+
+#[test_params_source(JsonLines("tests/test_data/test_requests.ndjson"))]
+fn test_json_lines(request: Request) {
+    assert!(!request.method.is_empty());
+    assert!(request.path.starts_with("/users"));
+    assert!((200..300).contains(&request.status));
+}
+
+#[test_params_source(JsonLines::<serde_json::Value>("tests/test_data/test_requests.ndjson"))]
+fn test_json_lines_explicit_type(value: serde_json::Value) {
+    assert!(value.is_object());
+}
@@ -35,3 +35,18 @@ struct User {
 fn test_json_response_user(user: User) {
     assert!(!user.username.is_empty());
 }
+
+#[derive(Deserialize, Debug)]
+struct Address {
+    city: String,
+}
+
+#[test_params_source(JsonResponse("https://jsonplaceholder.typicode.com/users/1", select = "/address"))]
+fn test_json_response_select(address: Address) {
+    assert!(!address.city.is_empty());
+}
+
+#[test_params_source(JsonResponse("https://jsonplaceholder.typicode.com/posts?_limit=3"), load = runtime)]
+fn test_json_response_runtime(post: Post) {
+    assert!(!post.title.is_empty());
+}
@@ -0,0 +1,28 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct LoginCase {
+    user: String,
+    allowed: bool,
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_suites.json", select = "/suites/login/cases"))]
+fn test_select_from_file(case: LoginCase) {
+    assert_eq!(case.allowed, case.user != "mallory");
+}
+
+#[test_params_source(JsonFile::<LoginCase>("tests/test_data/test_suites.json", select = "/suites/login/cases/1"))]
+fn test_select_array_index(case: LoginCase) {
+    assert_eq!(case.user, "mallory");
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_suites.json", LoginCase, select = "/suites/a~1b/cases"))]
+fn test_select_escaped_segment(case: LoginCase) {
+    assert_eq!(case.user, "bob");
+}
+
+#[test_params_source(JsonString(r#"{"data": {"values": [1, 2, 3]}}"#, select = "/data/values"))]
+fn test_select_from_string(val: u32) {
+    assert!(val > 0);
+}
@@ -65,3 +65,20 @@ fn test_json_string_vec_enum(e: TestEnum) {
 fn test_json_string_option(v: Option<u32>) {
     assert!(v.is_none() || v.is_some());
 }
+
+// Integers beyond 64 bits are read without losing digits
+#[test_params_source(JsonString("[170141183460469231731687303715884105727, -170141183460469231731687303715884105728]"))]
+fn test_json_string_128_bit_integers(v: i128) {
+    assert!(v == i128::MIN || v == i128::MAX);
+}
+
+#[test_params_source(JsonString(r#"[[340282366920938463463374607431768211455, "\u0000int:123"]]"#))]
+fn test_json_string_u128_and_text(v: u128, text: String) {
+    assert_eq!(v, u128::MAX);
+    assert_eq!(text, "\u{0}int:123");
+}
+
+#[test_params_source(JsonString(r#"["\u0000const:{ panic!(\"injected\"); 5u32 }"]"#))]
+fn test_json_string_text_is_not_code(v: String) {
+    assert_eq!(v, "\u{0}const:{ panic!(\"injected\"); 5u32 }");
+}
@@ -2,4 +2,18 @@ mod json_string;
 mod json_file;
 mod json_response;
 mod path_mask;
-mod path_mask_recursive;
\ No newline at end of file
+mod path_mask_recursive;
+mod yaml_file;
+mod yaml_string;
+
+mod toml_file;
+mod csv_file;
+mod json_lines;
+mod json_glob;
+mod json_select;
+mod runtime_load;
+mod named_cases;
+mod expected;
+mod markers;
+mod generated;
+mod results;
@@ -0,0 +1,35 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+fn divide(a: i32, b: i32) -> i32 {
+    assert!(a >= 0, "negative dividend");
+    if b == 0 {
+        panic!("divide by zero");
+    }
+    a / b
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_markers.json"))]
+fn test_markers_from_source(a: i32, b: i32) {
+    divide(a, b);
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_markers.json"), load = runtime)]
+fn test_markers_runtime(a: i32, b: i32) {
+    divide(a, b);
+}
+
+#[derive(Deserialize, Debug)]
+struct Division {
+    a: i32,
+    b: i32,
+}
+
+#[test_params_source(YamlString(r#"
+- {a: 4, b: 2}
+- {a: 4, b: 0, $should_panic: divide by zero}
+- {a: -4, b: 2, $ignore: not supported}
+"#))]
+fn test_markers_in_objects(division: Division) {
+    divide(division.a, division.b);
+}
@@ -0,0 +1,37 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_ddt_data.json"), name_field = "name")]
+fn test_name_field(person: Person) {
+    assert!(person.age >= 25);
+}
+
+#[derive(Deserialize, Debug)]
+struct LoginCase {
+    user: String,
+    allowed: bool,
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_suites.json", select = "/suites/login/cases"), name_field = "user")]
+fn test_name_field_with_select(case: LoginCase) {
+    assert_eq!(case.allowed, case.user == "alice");
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_ddt_data.json"), load = runtime, name_field = "name")]
+fn test_name_field_runtime(person: Person) {
+    assert!(!person.name.is_empty());
+}
+
+#[test]
+fn test_name_field_tests_exist() {
+    // Fails to compile if the names were not applied
+    let _ = test_name_field__alice;
+    let _ = test_name_field__bo1b;
+    let _ = test_name_field_with_select__mallory;
+}
@@ -0,0 +1,28 @@
+use rust_test_framework::test_params_source;
+use std::num::ParseIntError;
+
+#[test_params_source(JsonString(r#"["1", "2"]"#))]
+fn test_source_result(input: String) -> Result<(), ParseIntError> {
+    input.parse::<u32>()?;
+    Ok(())
+}
+
+// The return type isn't deserializable, so no test case can have an expected value
+#[test_params_source(JsonString(r#"["1", "2"]"#), load = runtime)]
+fn test_runtime_result(input: String) -> Result<(), ParseIntError> {
+    input.parse::<u32>()?;
+    Ok(())
+}
+
+#[test_params_source(JsonString(r#"[{"input": "1", "expected": {"Ok": 1}}, "2"]"#), load = runtime)]
+fn test_runtime_expected_result(input: String) -> Result<u32, String> {
+    input.parse().map_err(|e: ParseIntError| e.to_string())
+}
+
+#[test_params_source(Generated(strategy = 0..100u32, cases = 20))]
+fn test_generated_result(value: u32) -> Result<(), String> {
+    if value >= 100 {
+        return Err(format!("{} is too big", value));
+    }
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use rust_test_framework::__private::runtime::Case;
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Deserialize, Debug)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_ddt_data.json"), load = runtime)]
+fn test_runtime_json_file(person: Person) {
+    assert!(!person.name.is_empty());
+    assert!(person.age >= 25);
+}
+
+#[test_params_source(JsonString(r#"[[1, "one"], [2, "two"]]"#), load = runtime)]
+fn test_runtime_multiple_params(id: u32, label: &str) {
+    assert!(id > 0);
+    assert!(!label.is_empty());
+}
+
+#[derive(Deserialize, Debug)]
+struct Request {
+    method: String,
+    status: u16,
+}
+
+#[test_params_source(JsonLines("tests/test_data/test_requests.ndjson"), load = runtime)]
+fn test_runtime_json_lines(request: Request) {
+    assert!(!request.method.is_empty());
+    assert!(request.status >= 200);
+}
+
+#[derive(Deserialize, Debug)]
+struct User {
+    name: String,
+    age: u32,
+    score: f64,
+}
+
+#[test_params_source(CsvFile("tests/test_data/test_users.csv"), load = runtime)]
+fn test_runtime_csv_file(user: User) {
+    assert!(!user.name.is_empty());
+    assert!(user.age > 0);
+    assert!(user.score > 0.0);
+}
+
+#[test_params_source(PathMask("tests/test_data/*.yaml"), load = runtime)]
+fn test_runtime_path_mask(path: &Path) {
+    assert!(path.exists());
+}
+
+// The comma in the turbofish type belongs to the source, not to the options
+#[test_params_source(JsonString::<HashMap<String, u32>>(r#"[{"a": 1}, {"b": 2}]"#), load = runtime)]
+fn test_runtime_turbofish_with_comma(counts: HashMap<String, u32>) {
+    assert_eq!(counts.len(), 1);
+}
+
+#[test_params_source(JsonFile("tests/test_data/test_ddt_data.json"), load = compile_time)]
+fn test_explicit_compile_time(person: Person) {
+    assert!(!person.name.is_empty());
+}
+
+#[test]
+fn test_runtime_reports_all_failures() {
+    let cases = ["a", "b", "c"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| Case {
+            name: name.to_string(),
+            input: (i + 1).to_string(),
+            expected: None,
+            ignore: None,
+            should_panic: None,
+            csv_row: None,
+        })
+        .collect();
+    let result = std::panic::catch_unwind(|| {
+        rust_test_framework::__private::runtime::run_cases(cases, |case| {
+            let json = case.input.as_str();
+            assert_ne!(json, "1", "first case");
+            assert_ne!(json, "3", "third case");
+        });
+    });
+    let payload = result.unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("2 of 3 test cases failed"), "{}", message);
+    assert!(message.contains("a: assertion") && message.contains("first case"), "{}", message);
+    assert!(message.contains("c: assertion") && message.contains("third case"), "{}", message);
+    assert!(!message.contains("b: "), "{}", message);
+}
+
+#[test]
+fn test_runtime_markers() {
+    let case = |name: &str, ignore: Option<&str>, should_panic: Option<&str>| Case {
+        name: name.to_string(),
+        input: name.to_string(),
+        expected: None,
+        ignore: ignore.map(str::to_string),
+        should_panic: should_panic.map(str::to_string),
+        csv_row: None,
+    };
+    let cases = vec![
+        case("ignored", Some("JIRA-123"), None),
+        case("panics", None, Some("boom")),
+        case("no_panic", None, Some("")),
+        case("wrong_panic", None, Some("boom")),
+    ];
+    let result = std::panic::catch_unwind(|| {
+        rust_test_framework::__private::runtime::run_cases(cases, |case| match case.input.as_str() {
+            "ignored" => panic!("ignored cases must not run"),
+            "panics" => panic!("boom"),
+            "wrong_panic" => panic!("bang"),
+            _ => {}
+        });
+    });
+    let payload = result.unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("2 of 4 test cases failed"), "{}", message);
+    assert!(message.contains("no_panic: test case did not panic as expected"), "{}", message);
+    assert!(message.contains("wrong_panic: panic did not contain expected string"), "{}", message);
+}
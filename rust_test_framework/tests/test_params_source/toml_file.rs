@@ -0,0 +1,43 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct Addition {
+    a: i32,
+    b: i32,
+    expected: i32,
+}
+
+#[test_params_source(TomlFile("tests/test_data/test_cases.toml"))]
+fn test_toml_array_of_tables(case: Addition) {
+    assert_eq!(case.a + case.b, case.expected);
+}
+
+#[derive(Deserialize, Debug)]
+struct Address {
+    city: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Person {
+    name: String,
+    age: u32,
+    address: Address,
+}
+
+#[test_params_source(TomlFile::<Person>("tests/test_data/test_single_table.toml"))]
+fn test_toml_single_table(person: Person) {
+    assert_eq!(person.name, "Single");
+    assert_eq!(person.age, 50);
+    assert_eq!(person.address.city, "Lisbon");
+}
+
+#[derive(Deserialize, Debug)]
+struct Values {
+    values: Vec<i32>,
+}
+
+#[test_params_source(TomlFile("tests/test_data/test_single_array.toml"))]
+fn test_toml_single_array_of_values(values: Values) {
+    assert_eq!(values.values, vec![1, 2, 3]);
+}
@@ -0,0 +1,24 @@
+use rust_test_framework::test_params_source;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+#[test_params_source(YamlFile("tests/test_data/test_ddt_data.yaml"))]
+fn test_yaml_file_list(user: User) {
+    assert!(!user.name.is_empty());
+    assert!(user.age > 0);
+}
+
+#[test_params_source(YamlFile::<User>("tests/test_data/test_multi_document.yaml"))]
+fn test_yaml_file_multi_document(user: User) {
+    assert!(["Alice", "Bob", "Carol"].contains(&user.name.as_str()));
+}
+
+#[test_params_source(YamlFile("tests/test_data/test_vec_of_vec.json"))]
+fn test_yaml_file_json_is_valid_yaml(v: Vec<u32>) {
+    assert_eq!(v.len(), 3);
+}
@@ -0,0 +1,55 @@
+use rust_test_framework::test_params_source;
+
+#[test_params_source(YamlString("[1, 2, 3]"))]
+fn test_yaml_string_primitive(val: u32) {
+    assert!(val > 0);
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+#[test_params_source(YamlString(r#"
+- name: Alice
+  age: 30
+- name: Bob
+  age: 25
+"#))]
+fn test_yaml_string_struct(user: User) {
+    assert!(!user.name.is_empty());
+    assert!(user.age > 0);
+}
+
+#[test_params_source(YamlString(r#"
+name: Charlie
+age: 35
+"#))]
+fn test_yaml_string_single_struct(user: User) {
+    assert_eq!(user.name, "Charlie");
+    assert_eq!(user.age, 35);
+}
+
+#[test_params_source(YamlString(r#"
+- [1, one]
+- [2, two]
+"#))]
+fn test_yaml_string_tuple(id: u32, label: String) {
+    assert!(id > 0);
+    assert!(!label.is_empty());
+}
+
+#[test_params_source(YamlString("[1, 2, 3]"))]
+fn test_yaml_string_single_vec(v: Vec<u32>) {
+    assert_eq!(v, vec![1, 2, 3]);
+}
+
+#[test_params_source(YamlString(r#"
+[1, 2]
+---
+[3, 4, 5]
+"#))]
+fn test_yaml_string_multi_document_vec(v: Vec<u32>) {
+    assert!(v.len() == 2 || v.len() == 3);
+}
@@ -1,4 +1,18 @@
 use rust_test_framework::{test_fixture, test_params_source, setup, teardown};
+use std::cell::RefCell;
+
+thread_local! {
+    // Records the order hooks and tests run in, cleared by the last teardown of each test
+    static LOG: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn log(entry: &'static str) {
+    LOG.with(|log| log.borrow_mut().push(entry));
+}
+
+fn take_log() -> Vec<&'static str> {
+    LOG.with(|log| log.take())
+}
 
 #[test_fixture]
 mod tests {
@@ -36,6 +50,368 @@ mod tests {
     }
 }
 
+#[test_fixture]
+mod tests_context {
+    use rust_test_framework::test_params;
+    use super::*;
+
+    pub struct Database {
+        rows: Vec<String>,
+    }
+
+    #[setup]
+    fn open_database() -> Database {
+        Database { rows: vec!["first".to_string()] }
+    }
+
+    #[teardown]
+    fn close_database(db: Database) {
+        assert!(!db.rows.is_empty());
+    }
+
+    #[test]
+    fn test_shared_context(db: &Database) {
+        assert_eq!(db.rows, ["first"]);
+    }
+
+    #[test]
+    fn test_mutable_context(db: &mut Database) {
+        db.rows.push("second".to_string());
+        assert_eq!(db.rows.len(), 2);
+    }
+
+    #[test_params("a")]
+    #[test_params("b")]
+    fn test_params_with_context(db: &mut Database, row: &str) {
+        db.rows.push(row.to_string());
+        assert_eq!(db.rows.last().unwrap(), row);
+    }
+
+    #[test_params_source(JsonString(r#"[[1, "first"]]"#))]
+    fn test_source_with_context(count: usize, db: &Database, first: String) {
+        assert_eq!(db.rows.len(), count);
+        assert_eq!(db.rows[0], first);
+    }
+
+    #[test]
+    fn test_without_context() {
+        // The context is created and torn down all the same
+    }
+
+    #[tokio::test]
+    async fn test_async_context(db: &Database) {
+        tokio::task::yield_now().await;
+        assert_eq!(db.rows.len(), 1);
+    }
+}
+
+#[test_fixture]
+mod tests_ordered_hooks {
+    use super::*;
+
+    #[setup(order = 2)]
+    fn set_env() {
+        log("env");
+    }
+
+    #[setup]
+    fn init_logging() {
+        log("logging");
+    }
+
+    #[setup(order = 1)]
+    fn create_temp_dir() {
+        log("temp dir");
+    }
+
+    #[teardown(order = 1)]
+    fn remove_temp_dir() {
+        log("remove temp dir");
+    }
+
+    #[teardown]
+    fn flush_logs() {
+        log("flush logs");
+        assert_eq!(
+            take_log(),
+            ["logging", "temp dir", "env", "test", "restore env", "remove temp dir", "close files", "flush logs"]
+        );
+    }
+
+    // Same order as `flush_logs` but declared after it, so it runs before it
+    #[teardown]
+    fn close_files() {
+        log("close files");
+    }
+
+    #[teardown(order = 2)]
+    fn restore_env() {
+        log("restore env");
+    }
+
+    #[test]
+    fn test_hooks_run_in_order() {
+        LOG.with(|log| assert_eq!(*log.borrow(), ["logging", "temp dir", "env"]));
+        log("test");
+    }
+}
+
+#[test_fixture]
+mod tests_nested {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use rust_test_framework::before_all;
+    use super::*;
+
+    static OUTER_BEFORE_ALL: AtomicU32 = AtomicU32::new(0);
+
+    pub struct Server(&'static str);
+
+    #[before_all]
+    fn start() {
+        OUTER_BEFORE_ALL.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[setup]
+    fn outer_setup() -> Server {
+        log("outer setup");
+        Server("outer")
+    }
+
+    #[teardown]
+    fn outer_teardown(server: Server) {
+        log("outer teardown");
+        assert_eq!(server.0, "outer");
+        // The hooks of nested modules run inside of these
+        let log = take_log();
+        assert_eq!(log.first(), Some(&"outer setup"));
+        assert_eq!(log.last(), Some(&"outer teardown"));
+    }
+
+    #[test]
+    fn test_outer(server: &Server) {
+        log("test");
+        assert_eq!(server.0, "outer");
+    }
+
+    mod plain {
+        use super::*;
+
+        #[test]
+        fn test_inherits_hooks(server: &Server) {
+            assert_eq!(LOG.with(|log| log.borrow().clone()), ["outer setup"]);
+            assert_eq!(OUTER_BEFORE_ALL.load(Ordering::SeqCst), 1);
+            assert_eq!(server.0, "outer");
+        }
+    }
+
+    mod inner {
+        use super::*;
+
+        #[setup]
+        fn inner_setup() {
+            log("inner setup");
+        }
+
+        #[teardown]
+        fn inner_teardown() {
+            log("inner teardown");
+            assert_eq!(LOG.with(|log| log.borrow().clone()), ["outer setup", "inner setup", "test", "inner teardown"]);
+        }
+
+        #[test]
+        fn test_inner(server: &Server) {
+            log("test");
+            assert_eq!(server.0, "outer");
+        }
+
+        mod deeper {
+            use super::*;
+
+            #[test]
+            fn test_deeper() {
+                assert_eq!(LOG.with(|log| log.borrow().clone()), ["outer setup", "inner setup"]);
+                log("test");
+            }
+        }
+    }
+
+    #[test_fixture(uses = super::common_db)]
+    mod with_used_fixture {
+        use super::*;
+        use crate::common_db::Database;
+
+        #[test]
+        fn test_inherited_and_used(server: &Server, db: &Database) {
+            assert_eq!(server.0, "outer");
+            assert_eq!(db.rows, ["seed"]);
+        }
+    }
+
+    #[test_fixture]
+    mod with_own_before_all {
+        use super::*;
+
+        static INNER_BEFORE_ALL: AtomicU32 = AtomicU32::new(0);
+
+        #[before_all]
+        fn start_inner() {
+            assert_eq!(OUTER_BEFORE_ALL.load(Ordering::SeqCst), 1);
+            INNER_BEFORE_ALL.fetch_add(1, Ordering::SeqCst);
+        }
+
+        #[test]
+        fn test_both_before_all() {
+            assert_eq!(INNER_BEFORE_ALL.load(Ordering::SeqCst), 1);
+        }
+    }
+}
+
+#[test_fixture]
+pub(crate) mod common_db {
+    use super::*;
+
+    pub struct Database {
+        pub rows: Vec<&'static str>,
+    }
+
+    #[setup(order = -1)]
+    pub(crate) fn open_database() -> Database {
+        Database { rows: vec!["seed"] }
+    }
+
+    #[teardown(order = -1)]
+    pub(crate) fn close_database(db: Database) {
+        assert!(!db.rows.is_empty());
+    }
+
+    #[test]
+    fn test_own_fixture(db: &Database) {
+        assert_eq!(db.rows, ["seed"]);
+    }
+}
+
+#[test_fixture(uses = common_db)]
+mod tests_uses {
+    use super::common_db::Database;
+    use super::*;
+
+    pub struct TempDir(&'static str);
+
+    #[setup]
+    fn create_temp_dir() -> TempDir {
+        TempDir("/tmp/test")
+    }
+
+    #[test]
+    fn test_used_context(db: &mut Database, dir: &TempDir) {
+        db.rows.push(dir.0);
+        assert_eq!(db.rows, ["seed", "/tmp/test"]);
+    }
+
+    #[test_params_source(JsonString(r#"["a", "b"]"#))]
+    fn test_used_context_with_params(row: String, db: &Database) {
+        assert_eq!(db.rows.len(), 1);
+        assert!(!row.is_empty());
+    }
+}
+
+#[test_fixture(uses = [self::common_db])]
+mod tests_uses_list {
+    use super::common_db::Database;
+
+    #[test]
+    fn test_used_list(db: &Database) {
+        assert_eq!(db.rows, ["seed"]);
+    }
+}
+
+#[test_fixture]
+mod tests_before_all {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use rust_test_framework::{after_all, before_all, test_params};
+    use super::*;
+
+    static BEFORE_ALL_COUNT: AtomicU32 = AtomicU32::new(0);
+    static SETUP_COUNT: AtomicU32 = AtomicU32::new(0);
+    static FINISHED_COUNT: AtomicU32 = AtomicU32::new(0);
+
+    #[before_all]
+    fn start_server() {
+        // Give concurrent tests the chance to call it too
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        BEFORE_ALL_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[after_all]
+    fn stop_server() {
+        // A panic here fails the last test of the module
+        assert_eq!(BEFORE_ALL_COUNT.load(Ordering::SeqCst), 1);
+        assert!(FINISHED_COUNT.load(Ordering::SeqCst) <= 4);
+    }
+
+    #[setup]
+    fn count_setup() {
+        assert_eq!(BEFORE_ALL_COUNT.load(Ordering::SeqCst), 1);
+        SETUP_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[teardown]
+    fn count_finished() {
+        FINISHED_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_before_all_ran_once() {
+        assert_eq!(BEFORE_ALL_COUNT.load(Ordering::SeqCst), 1);
+    }
+
+    #[test_params(1)]
+    #[test_params(2)]
+    fn test_before_all_ran_once_per_module(value: u32) {
+        assert!(value > 0);
+        assert_eq!(BEFORE_ALL_COUNT.load(Ordering::SeqCst), 1);
+        assert!(SETUP_COUNT.load(Ordering::SeqCst) >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_async_before_all() {
+        assert_eq!(BEFORE_ALL_COUNT.load(Ordering::SeqCst), 1);
+    }
+}
+
+#[test_fixture]
+mod tests_before_all_failure {
+    use rust_test_framework::before_all;
+
+    #[before_all]
+    fn start_container() {
+        panic!("container did not start");
+    }
+
+    #[test]
+    #[should_panic(expected = "before_all failed: container did not start")]
+    fn test_first() {}
+
+    #[test]
+    #[should_panic(expected = "before_all failed: container did not start")]
+    fn test_second() {}
+}
+
+#[test_fixture]
+mod tests_after_all_failure {
+    use rust_test_framework::after_all;
+
+    #[after_all]
+    fn stop_container() {
+        panic!("container did not stop");
+    }
+
+    // The only test is the last one to finish
+    #[test]
+    #[should_panic(expected = "after_all failed: container did not stop")]
+    fn test_reports_after_all_failure() {}
+}
+
 #[test_fixture]
 mod tests_teardown_after_fail {
     use std::sync::atomic::{AtomicU32, Ordering};
@@ -75,23 +451,219 @@ mod tests_failure {
     }
 
     #[teardown]
-    fn teardown_not_ran() {
+    fn teardown_after_failed_setup() {
         TEARDOWN_RUN_ON_SETUP_FAIL.fetch_add(1, Ordering::SeqCst);
     }
 
     #[test]
-    #[should_panic(expected = "setup failed: intentional failure")]
-    fn test_failing_setup() {
+    #[should_panic(expected = "intentional failure")]
+    pub(crate) fn test_failing_setup() {
     }
 }
 
 #[test_fixture]
-mod tests_verify_failure {
+mod tests_partial_setup {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use super::*;
+
+    pub static RELEASED: AtomicU32 = AtomicU32::new(0);
+    pub static SECOND_RELEASED: AtomicU32 = AtomicU32::new(0);
+
+    pub struct Resource;
+    pub struct Second;
+
+    #[setup]
+    fn acquire() -> Resource {
+        Resource
+    }
+
+    #[setup(order = 1)]
+    fn acquire_second() -> Second {
+        panic!("second setup failed");
+    }
+
+    #[teardown]
+    fn release(_resource: Resource) {
+        RELEASED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[teardown(order = 1)]
+    fn release_second(_second: Second) {
+        SECOND_RELEASED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    #[should_panic(expected = "second setup failed")]
+    pub(crate) fn test_second_setup_fails(_resource: &Resource) {
+        unreachable!();
+    }
+}
+
+#[test_fixture]
+mod tests_teardown_by_setup_name {
+    use super::*;
+
+    pub struct Port(u16);
+
+    #[setup]
+    fn http() -> Port {
+        Port(80)
+    }
+
+    #[setup]
+    fn https() -> Port {
+        Port(443)
+    }
+
+    // Both setups return a `Port`, the parameter names the one to take
+    #[teardown]
+    fn close(https: Port) {
+        assert_eq!(https.0, 443);
+    }
+
+    #[test]
+    fn test_takes_named_context() {}
+}
+
+#[test_fixture]
+mod tests_unknown_location {
+    use super::*;
+
+    #[setup]
+    fn recover() {
+        let _ = std::panic::catch_unwind(|| panic!("recovered"));
+    }
+
+    #[teardown]
+    fn failing_teardown() {
+        panic!("teardown boom");
+    }
+
+    #[test]
+    #[should_panic(expected = "test failed: resumed")]
+    pub(crate) fn test_resumes_panic() {
+        // Doesn't go through the panic hook, so the location of the panic caught in setup must not be reported
+        std::panic::resume_unwind(Box::new(String::from("resumed")));
+    }
+}
+
+#[test_fixture]
+mod tests_phase_failures {
+    use std::cell::Cell;
+    use super::*;
+
+    thread_local! {
+        static FAIL_TEARDOWN: Cell<bool> = const { Cell::new(false) };
+    }
+
+    #[teardown]
+    fn failing_teardown() {
+        if FAIL_TEARDOWN.take() {
+            panic!("teardown boom");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "teardown failed: teardown boom")]
+    pub(crate) fn test_passes_teardown_fails() {
+        FAIL_TEARDOWN.set(true);
+    }
+
+    #[test]
+    #[should_panic(expected = "test failed: test boom")]
+    pub(crate) fn test_and_teardown_fail() {
+        FAIL_TEARDOWN.set(true);
+        panic!("test boom");
+    }
+
+    #[test]
+    #[should_panic]
+    pub(crate) fn test_panics_with_payload() {
+        std::panic::panic_any(42u32);
+    }
+}
+
+#[test_fixture]
+mod tests_setup_payload {
+    use super::*;
+
+    #[setup]
+    fn failing_setup() {
+        std::panic::panic_any(7u8);
+    }
+
+    #[test]
+    #[should_panic]
+    pub(crate) fn test_setup_panics_with_payload() {}
+}
+
+mod tests_phase_reports {
+    use std::panic::catch_unwind;
     use std::sync::atomic::Ordering;
 
+    fn panic_message(test: fn()) -> String {
+        let payload = catch_unwind(test).unwrap_err();
+        // The panic of a setup failing alone is resumed as it is, e.g. a `&str`
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|message| message.to_string()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_teardown_runs_after_failed_setup() {
+        let released = super::tests_failure::TEARDOWN_RUN_ON_SETUP_FAIL.load(Ordering::SeqCst);
+        let message = panic_message(super::tests_failure::test_failing_setup);
+        assert_eq!(message, "intentional failure");
+        assert!(super::tests_failure::TEARDOWN_RUN_ON_SETUP_FAIL.load(Ordering::SeqCst) > released);
+    }
+
+    #[test]
+    fn test_teardown_of_completed_setups_only() {
+        use super::tests_partial_setup::{RELEASED, SECOND_RELEASED};
+        let released = RELEASED.load(Ordering::SeqCst);
+        let message = panic_message(super::tests_partial_setup::test_second_setup_fails);
+        assert_eq!(message, "second setup failed");
+        assert!(RELEASED.load(Ordering::SeqCst) > released);
+        assert_eq!(SECOND_RELEASED.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_teardown_failure_reported() {
+        let message = panic_message(super::tests_phase_failures::test_passes_teardown_fails);
+        assert!(message.starts_with("teardown failed: teardown boom\n  in `failing_teardown` at "), "{}", message);
+        assert!(!message.contains("test failed"), "{}", message);
+    }
+
+    #[test]
+    fn test_test_and_teardown_failures_reported() {
+        let message = panic_message(super::tests_phase_failures::test_and_teardown_fail);
+        let lines: Vec<_> = message.lines().collect();
+        assert_eq!(lines.len(), 4, "{}", message);
+        assert_eq!(lines[0], "test failed: test boom");
+        assert!(lines[1].starts_with("  at ") && lines[1].contains("main.rs:"), "{}", message);
+        assert_eq!(lines[2], "teardown failed: teardown boom");
+        assert!(lines[3].starts_with("  in `failing_teardown` at "), "{}", message);
+    }
+
+    #[test]
+    fn test_unknown_location_reported() {
+        let message = panic_message(super::tests_unknown_location::test_resumes_panic);
+        let lines: Vec<_> = message.lines().collect();
+        assert_eq!(lines[..2], ["test failed: resumed", "  at unknown location"], "{}", message);
+    }
+
+    #[test]
+    fn test_setup_panic_resumed_as_it_is() {
+        let payload = catch_unwind(super::tests_setup_payload::test_setup_panics_with_payload).unwrap_err();
+        assert_eq!(payload.downcast_ref::<u8>(), Some(&7));
+    }
+
     #[test]
-    fn test_verify_no_teardown() {
-        assert_eq!(super::tests_failure::TEARDOWN_RUN_ON_SETUP_FAIL.load(Ordering::SeqCst), 0);
+    fn test_test_panic_resumed_as_it_is() {
+        let payload = catch_unwind(super::tests_phase_failures::test_panics_with_payload).unwrap_err();
+        assert_eq!(payload.downcast_ref::<u32>(), Some(&42));
     }
 }
 
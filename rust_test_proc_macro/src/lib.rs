@@ -5,6 +5,12 @@ use rust_test_core::SourceType as SourceType;
 
 /// Generates tests based on provided inlined parameters.
 /// (must implement/derive `serde::Deserialize` or be a built-in type).
+/// Values are literals, arrays, `vec![...]`, tuples, references, struct initializers,
+/// tuple structs named after the parameter type and enum variants.
+/// Use `name = "..."` to name the generated test instead of deriving the name from the value.
+/// If the function returns a value, `expect = ...` compares it with the given value.
+/// `ignore = "reason"` and `should_panic = "message"` mark the generated test with `#[ignore]` and `#[should_panic]`.
+/// Ranges such as `1..=10`, `'a'..='f'` and `range(0, 100, step = 10)` generate a test per element.
 /// # Example
 /// ```rust
 /// # use rust_test_proc_macro as rust_test_framework;
@@ -12,7 +18,8 @@ use rust_test_core::SourceType as SourceType;
 ///
 /// #[test_params(1)]
 /// #[test_params(2)]
-/// #[test_params(3)]
+/// #[test_params(name = "three", 3)]
+/// #[test_params(10..=12)]
 /// fn test_numbers(item: u32) {
 ///     assert!(item > 0);
 /// }
@@ -24,11 +31,68 @@ pub fn test_params(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Generates a test that calls the function with the given Rust expressions, one per parameter.
+///
+/// Unlike `#[test_params]`, the arguments are not converted to JSON, so the parameter types
+/// don't need to implement `serde::Deserialize`. Tests are named `<function>__case_1`, `<function>__case_2`, ...
+/// in the order of the attributes, or with `name = "..."`. The `expect`, `ignore` and `should_panic`
+/// options work the same way as with `#[test_params]`, and `expect` takes any expression.
+/// # Example
+/// ```rust
+/// # use rust_test_proc_macro as rust_test_framework;
+/// use rust_test_framework::test_case;
+/// use std::time::Duration;
+///
+/// #[test_case(Duration::from_secs(3), 3)]
+/// #[test_case(Duration::from_millis(1500), 1, name = "rounds_down")]
+/// fn test_whole_seconds(timeout: Duration, seconds: u64) {
+///     assert_eq!(timeout.as_secs(), seconds);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test_case(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attributes::test_case(attr.into(), item.into())
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
+}
+
+/// Generates a test for every combination of the provided values,
+/// with one list of values per parameter, or a range such as `1..=10`.
+/// Add `strategy = pairwise` or `strategy = nwise(N)` to only generate enough combinations
+/// for every combination of values of any 2 (or N) parameters to be tested.
+/// # Example
+/// ```rust
+/// # use rust_test_proc_macro as rust_test_framework;
+/// use rust_test_framework::test_matrix;
+///
+/// // Generates 6 tests: test_combinations__1_a_true, test_combinations__1_a_false, ...
+/// #[test_matrix([1, 2, 3], ["a", "b"], [true, false])]
+/// fn test_combinations(id: u32, label: &str, enabled: bool) {
+///     assert!(id > 0);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test_matrix(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attributes::test_matrix(attr.into(), item.into())
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
+}
+
 /// Generates tests based on a provided source and model of that data
 /// (must implement/derive `serde::Deserialize` or be a built-in type).
 /// # Arguments
 /// - `source_type`: A [`SourceType`] variant 
 /// can be fully qualified or via just the variant name.
+/// - `load = runtime` (optional): read the source when the test runs instead of at compile time.
+/// A single test runs all cases and reports every failed case.
+/// - `name_field = "..."` (optional): name each generated test after this field of its test case.
+///
+/// If the function returns a value, it is compared with the `expected` field of each test case,
+/// or with `expected` of an `{"input": ..., "expected": ...}` envelope.
+/// The `$ignore` and `$should_panic` keys of a test case mark its test with `#[ignore]` and `#[should_panic]`.
+///
+/// `Generated(strategy = ..., cases = 256, seed = 42)` runs the function with random inputs instead,
+/// and shrinks the first failing input to a minimal counterexample.
 /// # Example
 /// ```rust
 /// # use rust_test_proc_macro as rust_test_framework;
@@ -114,7 +178,61 @@ pub fn teardown(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
-/// Marks a module as a test fixture, enabling `#[setup]` and `#[teardown]` functionality.
+/// Marks a function to be run once before the first test in a `#[test_fixture]`.
+///
+/// # Example
+/// ```rust,ignore
+/// use rust_test_framework::{test_fixture, before_all};
+///
+/// #[test_fixture]
+/// mod my_tests {
+///     #[before_all]
+///     fn start_server() {
+///         // runs once, whichever test comes first
+///     }
+///
+///     #[test]
+///     fn some_test() {
+///         // ...
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn before_all(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attributes::before_all(attr.into(), item.into())
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
+}
+
+/// Marks a function to be run once after the last test of a `#[test_fixture]` has finished.
+/// A panic in it fails that test.
+///
+/// # Example
+/// ```rust,ignore
+/// use rust_test_framework::{test_fixture, after_all};
+///
+/// #[test_fixture]
+/// mod my_tests {
+///     #[after_all]
+///     fn stop_server() {
+///         // runs once after the last test
+///     }
+///
+///     #[test]
+///     fn some_test() {
+///         // ...
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn after_all(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attributes::after_all(attr.into(), item.into())
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
+}
+
+/// Marks a module as a test fixture, enabling `#[setup]`, `#[teardown]`, `#[before_all]` and `#[after_all]` functionality.
+/// With `uses = path`, the module also runs the setup and teardown functions of another fixture module.
 ///
 /// # Example
 /// ```rust
@@ -152,3 +270,27 @@ pub fn test_fixture(attr: TokenStream, item: TokenStream) -> TokenStream {
 pub fn rust_test_seen_value(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
+
+/// Internal use only.
+#[proc_macro_attribute]
+pub fn rust_test_seen_expr(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+/// Internal use only.
+#[proc_macro_attribute]
+pub fn rust_test_seen_name(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+/// Internal use only.
+#[proc_macro_attribute]
+pub fn rust_test_runtime(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+/// Internal use only.
+#[proc_macro_attribute]
+pub fn rust_test_enter(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}